@@ -0,0 +1,101 @@
+//! Discover the solutions of every year crate compiled into the runner.
+//!
+//! `cargo-aoc` only records registered solutions while building a single year, so the year crates
+//! are scanned for `#[aoc(dayN, partM)]` attributes instead, and a table calling the matching
+//! `Factory` trait methods is generated.
+
+#[path = "src/attribute.rs"]
+mod attribute;
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use attribute::parse_attribute;
+
+struct Entry {
+    year: u32,
+    day: u32,
+    part: u32,
+    name: Option<String>,
+}
+
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+
+    let mut entries = vec![];
+    let mut years = vec![];
+    for dir in fs::read_dir(&root).unwrap() {
+        let path = dir.unwrap().path();
+        let Some(year) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix("aoc"))
+            .and_then(|y| y.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        if env::var_os(format!("CARGO_FEATURE_AOC{year}")).is_none() {
+            println!("cargo::warning=aoc{year} is not enabled in the runner, skipping it");
+            continue;
+        }
+        years.push(year);
+
+        let src = path.join("src");
+        println!("cargo::rerun-if-changed={}", src.display());
+        for file in fs::read_dir(&src).unwrap() {
+            let file = file.unwrap().path();
+            if file.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            for line in fs::read_to_string(&file).unwrap().lines() {
+                if let Some((day, part, name)) = parse_attribute(line) {
+                    entries.push(Entry {
+                        year,
+                        day,
+                        part,
+                        name,
+                    });
+                }
+            }
+        }
+    }
+    years.sort_unstable();
+    entries.sort_by_key(|e| (e.year, e.day, e.part, e.name.is_some()));
+
+    let mut out = String::from("&[\n");
+    for Entry {
+        year,
+        day,
+        part,
+        name,
+    } in &entries
+    {
+        let (trait_name, fn_name) = match name {
+            Some(n) => (
+                format!("Day{day}Part{part}{}", n.to_uppercase()),
+                format!("day{day}_part{part}_{}", n.to_lowercase()),
+            ),
+            None => (
+                format!("Day{day}Part{part}"),
+                format!("day{day}_part{part}"),
+            ),
+        };
+        writeln!(
+            out,
+            "    Solution {{ year: {year}, day: {day}, part: {part}, name: {name:?}, \
+             factory: <aoc{year}::Factory as aoc{year}::{trait_name}>::{fn_name} }},"
+        )
+        .unwrap();
+    }
+    out.push(']');
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+    fs::write(Path::new(&out_dir).join("years.rs"), format!("&{years:?}")).unwrap();
+}
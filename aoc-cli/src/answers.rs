@@ -0,0 +1,157 @@
+//! The accepted answers of each year, recorded in `aocYYYY/answers.txt`.
+//!
+//! Each line holds `DAY PART ANSWER`, and applies to every variant of that part. A variant that is
+//! expected to disagree, like a known wrong attempt kept for comparison, gets its own line as
+//! `DAY PART/VARIANT ANSWER`. Empty lines and lines starting with `#` are ignored.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::answers_path;
+use crate::solutions::Solution;
+
+/// The day, part and variant an answer was recorded for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<Key, String>,
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = HashMap::new();
+        for (n, line) in s.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.splitn(3, ' ').map(str::trim);
+            let (Some(day), Some(part), Some(answer)) = (words.next(), words.next(), words.next())
+            else {
+                return Err(format!("Line {n}: expected DAY PART ANSWER"));
+            };
+            let (part, variant) = match part.split_once('/') {
+                Some((part, variant)) => (part, Some(variant.to_string())),
+                None => (part, None),
+            };
+            let key = Key {
+                day: day
+                    .parse()
+                    .map_err(|_| format!("Line {n}: invalid day [{day}]"))?,
+                part: part
+                    .parse()
+                    .map_err(|_| format!("Line {n}: invalid part [{part}]"))?,
+                variant,
+            };
+            if answers.insert(key, answer.to_string()).is_some() {
+                return Err(format!("Line {n}: answer recorded twice"));
+            }
+        }
+        Ok(Self { answers })
+    }
+}
+
+impl Answers {
+    /// Load the answers recorded for a year under `root`. A year without an answers file has no
+    /// recorded answers.
+    pub fn load(root: &Path, year: u32) -> Result<Self, String> {
+        let path = answers_path(root, year);
+        match fs::read_to_string(&path) {
+            Ok(s) => s.parse().map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// The answer a solution is expected to give, if one was recorded
+    pub fn get(&self, solution: &Solution) -> Option<&str> {
+        let mut key = Key {
+            day: solution.day,
+            part: solution.part,
+            variant: solution.name.map(String::from),
+        };
+        if let Some(answer) = self.answers.get(&key) {
+            return Some(answer);
+        }
+        key.variant = None;
+        self.answers.get(&key).map(String::as_str)
+    }
+
+    /// Whether a variant has its own answer recorded, because it is known to disagree with the
+    /// other variants of its part
+    pub fn expects_disagreement(&self, solution: &Solution) -> bool {
+        solution.name.is_some_and(|name| {
+            self.answers.contains_key(&Key {
+                day: solution.day,
+                part: solution.part,
+                variant: Some(name.to_string()),
+            })
+        })
+    }
+
+    /// Every recorded answer, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &str)> {
+        self.answers.iter().map(|(k, a)| (k, a.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(part: u32, name: Option<&'static str>) -> Solution {
+        Solution {
+            year: 2023,
+            day: 1,
+            part,
+            name,
+            factory: |_| Err("unused".into()),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = "
+            # DAY PART ANSWER
+            1 1 56397
+
+            1 2 55701
+            1 2/naive 55725
+            "
+        .parse()
+        .unwrap();
+        assert_eq!(answers.get(&solution(1, None)), Some("56397"));
+        assert_eq!(answers.get(&solution(2, Some("correct"))), Some("55701"));
+        assert_eq!(answers.get(&solution(2, Some("naive"))), Some("55725"));
+        assert_eq!(answers.get(&solution(3, None)), None);
+        assert!(answers.expects_disagreement(&solution(2, Some("naive"))));
+        assert!(!answers.expects_disagreement(&solution(2, Some("correct"))));
+        assert!(!answers.expects_disagreement(&solution(1, None)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "1 1".parse::<Answers>(),
+            Err(String::from("Line 1: expected DAY PART ANSWER"))
+        );
+        assert_eq!(
+            "1 x 5".parse::<Answers>(),
+            Err(String::from("Line 1: invalid part [x]"))
+        );
+        assert_eq!(
+            "1 1 5\n1 1 6".parse::<Answers>(),
+            Err(String::from("Line 2: answer recorded twice"))
+        );
+    }
+}
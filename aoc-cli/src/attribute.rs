@@ -0,0 +1,12 @@
+//! The `#[aoc(dayN, partM)]` attributes that register solutions. This is also compiled into
+//! `build.rs`, so it only uses `std`.
+
+/// Parse the day, part and variant name of an `#[aoc(day18, part1, Vec)]` attribute
+pub fn parse_attribute(line: &str) -> Option<(u32, u32, Option<String>)> {
+    let args = line.trim().strip_prefix("#[aoc(")?.strip_suffix(")]")?;
+    let mut args = args.split(',').map(str::trim);
+    let day = args.next()?.strip_prefix("day")?.parse().ok()?;
+    let part = args.next()?.strip_prefix("part")?.parse().ok()?;
+    let name = args.next().map(String::from);
+    Some((day, part, name))
+}
@@ -0,0 +1,317 @@
+//! Time the generator and the solver of each solution over repeated runs, and keep a history of
+//! the results per git commit to spot regressions.
+//!
+//! The history is a tab separated file with one line per commit and solution:
+//! `COMMIT YEAR DAY PART VARIANT GENERATOR_NS RUNNER_NS`, where an empty variant is written as `-`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::run::{run, Outcome, DEFAULT_TIMEOUT};
+use crate::solutions::Solution;
+
+/// How many times to run each solution, and for how long at most
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Runs whose timings are thrown away, to warm up caches and the allocator
+    pub warmup: u32,
+    /// Runs whose timings are kept
+    pub samples: u32,
+    /// The time limit of each run
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warmup: 2,
+            samples: 10,
+            timeout: Some(DEFAULT_TIMEOUT),
+        }
+    }
+}
+
+/// The median time of each step of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub generator: Duration,
+    pub runner: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.generator + self.runner
+    }
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort_unstable();
+    let mid = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        (durations[mid - 1] + durations[mid]) / 2
+    } else {
+        durations[mid]
+    }
+}
+
+/// Time a solution against the given input. Fails with the outcome of the first run that did not
+/// produce an answer.
+pub fn bench(
+    solution: &'static Solution,
+    input: &str,
+    options: Options,
+) -> Result<Timing, Outcome> {
+    assert!(options.samples > 0, "At least one sample is needed");
+    let mut generator = vec![];
+    let mut runner = vec![];
+    for i in 0..options.warmup + options.samples {
+        let report = run(solution, input, options.timeout);
+        if !matches!(report.outcome, Outcome::Answer(_)) {
+            return Err(report.outcome);
+        }
+        if i >= options.warmup {
+            generator.push(report.generator);
+            runner.push(report.runner);
+        }
+    }
+    Ok(Timing {
+        generator: median(generator),
+        runner: median(runner),
+    })
+}
+
+/// One line of the history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub commit: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<String>,
+    pub timing: Timing,
+}
+
+impl Entry {
+    pub fn new(commit: &str, solution: &Solution, timing: Timing) -> Self {
+        Self {
+            commit: commit.to_string(),
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            name: solution.name.map(String::from),
+            timing,
+        }
+    }
+
+    fn is_for(&self, solution: &Solution) -> bool {
+        (self.year, self.day, self.part, self.name.as_deref())
+            == (solution.year, solution.day, solution.part, solution.name)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let mut next = || fields.next();
+        let commit = next()?.to_string();
+        let year = next()?.parse().ok()?;
+        let day = next()?.parse().ok()?;
+        let part = next()?.parse().ok()?;
+        let name = Some(next()?).filter(|n| *n != "-").map(String::from);
+        let generator = Duration::from_nanos(next()?.parse().ok()?);
+        let runner = Duration::from_nanos(next()?.parse().ok()?);
+        Some(Self {
+            commit,
+            year,
+            day,
+            part,
+            name,
+            timing: Timing { generator, runner },
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.commit,
+            self.year,
+            self.day,
+            self.part,
+            self.name.as_deref().unwrap_or("-"),
+            self.timing.generator.as_nanos(),
+            self.timing.runner.as_nanos(),
+        )
+    }
+}
+
+/// Every timing recorded so far, oldest first
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(n, l)| Entry::parse(l).ok_or_else(|| format!("Invalid history line {}", n + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { entries })
+    }
+
+    /// Load the history, which is empty before the first benchmark
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::new();
+        for entry in &self.entries {
+            out.push_str(&entry.to_line());
+            out.push('\n');
+        }
+        fs::write(path, out)
+    }
+
+    /// Record a timing, replacing the one of the same commit and solution if there was one
+    pub fn record(&mut self, entry: Entry) {
+        self.entries.retain(|e| {
+            e.commit != entry.commit
+                || (e.year, e.day, e.part, &e.name)
+                    != (entry.year, entry.day, entry.part, &entry.name)
+        });
+        self.entries.push(entry);
+    }
+
+    /// The latest timing of a solution recorded for another commit than `commit`
+    pub fn previous(&self, commit: &str, solution: &Solution) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.commit != commit && e.is_for(solution))
+    }
+}
+
+/// By how much `timing` is slower than `previous`, as a fraction of `previous`
+pub fn change(previous: Timing, timing: Timing) -> f64 {
+    let previous = previous.total().as_secs_f64();
+    let current = timing.total().as_secs_f64();
+    if previous == 0.0 {
+        0.0
+    } else {
+        (current - previous) / previous
+    }
+}
+
+/// The path of the history file, which is kept out of the repository
+pub fn history_path(root: &Path) -> PathBuf {
+    root.join("aoc-cli/bench-history.tsv")
+}
+
+/// The commit being benchmarked, suffixed with `-dirty` when there are uncommitted changes so that
+/// they do not overwrite the timings of the commit itself
+pub fn current_commit(root: &Path) -> Result<String, String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .map_err(|e| format!("Could not run git: {e}"))
+            .and_then(|o| {
+                if o.status.success() {
+                    Ok(String::from_utf8_lossy(&o.stdout).trim().to_string())
+                } else {
+                    Err(format!(
+                        "git {} failed: {}",
+                        args.join(" "),
+                        String::from_utf8_lossy(&o.stderr).trim()
+                    ))
+                }
+            })
+    };
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Ok(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(name: Option<&'static str>) -> Solution {
+        Solution {
+            year: 2015,
+            day: 19,
+            part: 1,
+            name,
+            factory: |_| Err("unused".into()),
+        }
+    }
+
+    fn timing(generator: u64, runner: u64) -> Timing {
+        Timing {
+            generator: Duration::from_nanos(generator),
+            runner: Duration::from_nanos(runner),
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        let ms = |v: &[u64]| v.iter().map(|n| Duration::from_millis(*n)).collect();
+        assert_eq!(median(ms(&[3, 1, 2])), Duration::from_millis(2));
+        assert_eq!(median(ms(&[4, 1, 2, 3])), Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let mut history = History::default();
+        history.record(Entry::new("abc", &solution(None), timing(1, 2)));
+        history.record(Entry::new("abc", &solution(Some("Vec")), timing(3, 4)));
+
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.tsv", std::process::id()));
+        history.save(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            "abc\t2015\t19\t1\t-\t1\t2\nabc\t2015\t19\t1\tVec\t3\t4\n"
+        );
+        assert_eq!(History::load(&path), Ok(history));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            History::parse("abc\t2015\tx"),
+            Err(String::from("Invalid history line 1"))
+        );
+    }
+
+    #[test]
+    fn test_previous() {
+        let s = solution(Some("Vec"));
+        let mut history = History::default();
+        history.record(Entry::new("old", &s, timing(10, 100)));
+        history.record(Entry::new("older", &solution(None), timing(1, 1)));
+        history.record(Entry::new("new", &s, timing(10, 200)));
+        // Benchmarking the same commit again replaces its timing
+        history.record(Entry::new("new", &s, timing(10, 150)));
+
+        assert_eq!(history.entries.len(), 3);
+        let previous = history.previous("new", &s).unwrap();
+        assert_eq!(previous.commit, "old");
+        assert_eq!(history.previous("old", &s).unwrap().commit, "new");
+        assert_eq!(history.previous("new", &solution(Some("HashSet"))), None);
+
+        assert!((change(previous.timing, timing(10, 150)) - 50.0 / 110.0).abs() < 1e-9);
+        assert_eq!(change(timing(0, 0), timing(1, 1)), 0.0);
+    }
+}
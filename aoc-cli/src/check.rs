@@ -0,0 +1,218 @@
+//! Run every variant of a part on the same inputs and report the ones that disagree.
+//!
+//! The inputs are the puzzle examples saved under `aocYYYY/examples/dayN`, followed by the puzzle
+//! input. Examples are run through both parts, so a variant that fails on an input is only
+//! reported when another one succeeds.
+
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::Answers;
+use crate::examples;
+use crate::run::{read_input, run, Outcome};
+use crate::solutions::{Selection, Solution};
+
+/// An input every variant of a part is run on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    /// `example K` or `input`
+    pub name: String,
+    pub text: String,
+}
+
+/// The examples of a day in order, followed by its puzzle input when either was saved
+pub fn inputs(root: &Path, solution: &Solution) -> Result<Vec<Input>, String> {
+    let mut inputs: Vec<_> = examples::load(root, solution.year, solution.day)?
+        .into_iter()
+        .map(|example| Input {
+            name: format!("example {}", example.number),
+            text: example.input,
+        })
+        .collect();
+    if let Some(text) = read_input(root, solution) {
+        inputs.push(Input {
+            name: String::from("input"),
+            text,
+        });
+    }
+    Ok(inputs)
+}
+
+/// The selected parts that have several variants, each with its variants in order
+pub fn variants(selection: Selection) -> Vec<Vec<&'static Solution>> {
+    let mut parts: Vec<Vec<&'static Solution>> = vec![];
+    for solution in selection.solutions() {
+        match parts.last_mut() {
+            Some(part)
+                if (part[0].year, part[0].day, part[0].part)
+                    == (solution.year, solution.day, solution.part) =>
+            {
+                part.push(solution);
+            }
+            _ => parts.push(vec![solution]),
+        }
+    }
+    parts.retain(|part| part.len() > 1);
+    parts
+}
+
+/// The variants of a part that gave different outcomes on the same input
+pub struct Disagreement {
+    pub input: String,
+    pub outcomes: Vec<(&'static Solution, Outcome)>,
+    /// Whether the variants that disagree have their own answer recorded in `answers.txt`, as a
+    /// known wrong attempt would
+    pub known: bool,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (first, _) = self.outcomes[0];
+        write!(
+            f,
+            "{} day {} part {} on {}:",
+            first.year, first.day, first.part, self.input
+        )?;
+        for (solution, outcome) in &self.outcomes {
+            write!(f, " {} = {outcome};", solution.name.unwrap_or("default"))?;
+        }
+        if self.known {
+            write!(f, " (known)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether the outcomes tell the same story: the same answer everywhere, or a failure everywhere
+/// since an example of one part often does not suit the other
+fn agree<'a>(outcomes: impl IntoIterator<Item = &'a Outcome>) -> bool {
+    let answers: Vec<_> = outcomes
+        .into_iter()
+        .map(|o| match o {
+            Outcome::Answer(a) => Some(a),
+            _ => None,
+        })
+        .collect();
+    answers.windows(2).all(|w| w[0] == w[1])
+}
+
+/// Run every variant of a part on each input, giving up on a run after `timeout`, and collect the
+/// inputs they disagree on
+pub fn cross_check(
+    variants: &[&'static Solution],
+    inputs: &[Input],
+    answers: &Answers,
+    timeout: Option<Duration>,
+) -> Vec<Disagreement> {
+    let mut disagreements = vec![];
+    for input in inputs {
+        let outcomes: Vec<_> = variants
+            .iter()
+            .map(|s| (*s, run(s, &input.text, timeout).outcome))
+            .collect();
+        if agree(outcomes.iter().map(|(_, o)| o)) {
+            continue;
+        }
+        let known = agree(
+            outcomes
+                .iter()
+                .filter(|(s, _)| !answers.expects_disagreement(s))
+                .map(|(_, o)| o),
+        );
+        disagreements.push(Disagreement {
+            input: input.name.clone(),
+            outcomes,
+            known,
+        });
+    }
+    disagreements
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use aoc_runner::{ArcStr, Runner};
+
+    use super::*;
+
+    /// A runner answering with the length of its input, or with `offset` more than that
+    struct Length<const OFFSET: usize>(usize);
+
+    impl<const OFFSET: usize> Runner for Length<OFFSET> {
+        fn gen(input: ArcStr) -> Self {
+            let input: &str = std::borrow::Borrow::borrow(&input);
+            assert!(!input.is_empty(), "empty input");
+            Self(input.len() + OFFSET)
+        }
+
+        fn run(&self) -> Box<dyn Display> {
+            Box::new(self.0)
+        }
+
+        fn bench(&self, _: fn(&dyn Display)) {}
+    }
+
+    fn solution<const OFFSET: usize>(name: &'static str) -> Solution {
+        Solution {
+            year: 2023,
+            day: 1,
+            part: 2,
+            name: Some(name),
+            factory: |input| Ok(Box::new(Length::<OFFSET>::try_gen(input)?)),
+        }
+    }
+
+    fn input(name: &str, text: &str) -> Input {
+        Input {
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_agree() {
+        let answer = |a: &str| Outcome::Answer(a.to_string());
+        let failed = || Outcome::Failed(String::from("running: not yet implemented"));
+        assert!(agree(&[answer("1"), answer("1")]));
+        assert!(agree(&[failed(), failed()]));
+        assert!(!agree(&[answer("1"), answer("2")]));
+        assert!(!agree(&[answer("1"), failed()]));
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let variants: [&'static Solution; 2] = [
+            Box::leak(Box::new(solution::<0>("correct"))),
+            Box::leak(Box::new(solution::<1>("naive"))),
+        ];
+        let inputs = [input("example 1", ""), input("input", "abc")];
+
+        let disagreements = cross_check(&variants, &inputs, &Answers::default(), None);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(
+            disagreements[0].to_string(),
+            "2023 day 1 part 2 on input: correct = 3; naive = 4;"
+        );
+        assert!(!disagreements[0].known);
+
+        let answers: Answers = "1 2 3\n1 2/naive 4".parse().unwrap();
+        let disagreements = cross_check(&variants, &inputs, &answers, None);
+        assert!(disagreements[0].known);
+        assert!(disagreements[0].to_string().ends_with("(known)"));
+    }
+
+    #[cfg(feature = "aoc2023")]
+    #[test]
+    fn test_variants() {
+        let selection = Selection {
+            year: Some(2023),
+            ..Selection::default()
+        };
+        let parts = variants(selection);
+        assert_eq!(parts.len(), 1);
+        let names: Vec<_> = parts[0].iter().map(|s| s.name).collect();
+        assert_eq!(names, vec![Some("naive"), Some("correct")]);
+    }
+}
@@ -0,0 +1,319 @@
+//! The examples of the puzzle texts, kept as fixtures under `aocYYYY/examples/dayN`.
+//!
+//! Each example is saved exactly as the puzzle shows it in `K.txt`, numbered in the order of the
+//! puzzle text, and the answers the text gives for them are recorded in `answers.txt`, one
+//! `EXAMPLE PART ANSWER` per line. They can be extracted from a puzzle page saved from the browser,
+//! where the examples are the `<pre><code>` blocks and the answers are emphasized as
+//! `<code><em>42</em></code>`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::examples_dir;
+
+/// An example of a puzzle, with the answer the puzzle text gives for each part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub number: u32,
+    pub input: String,
+    pub answers: BTreeMap<u32, String>,
+}
+
+/// Replace the HTML entities that show up in puzzle texts
+fn decode(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let Some(end) = rest.find(';') else { break };
+        let decoded = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            e => e
+                .strip_prefix('#')
+                .and_then(|n| n.parse().ok())
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The text of some HTML, without its tags
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode(&text)
+}
+
+/// What a puzzle text holds that is worth extracting
+enum Item {
+    Example(String),
+    Answer(String),
+}
+
+/// The examples and emphasized answers of an article, in the order they appear
+fn items(article: &str) -> Vec<Item> {
+    const EXAMPLE: (&str, &str) = ("<pre><code>", "</code></pre>");
+    const ANSWER: (&str, &str) = ("<code><em>", "</em></code>");
+
+    let mut items = vec![];
+    let mut rest = article;
+    loop {
+        let next = [EXAMPLE, ANSWER]
+            .into_iter()
+            .filter_map(|(open, close)| Some((rest.find(open)?, open, close)))
+            .min_by_key(|(i, _, _)| *i);
+        let Some((i, open, close)) = next else { break };
+        let start = i + open.len();
+        let Some(len) = rest[start..].find(close) else {
+            break;
+        };
+        let content = text(&rest[start..start + len]);
+        items.push(if open == EXAMPLE.0 {
+            Item::Example(content)
+        } else {
+            Item::Answer(content)
+        });
+        rest = &rest[start + len + close.len()..];
+    }
+    items
+}
+
+/// Extract the examples of a saved puzzle page. The puzzle has an article per part, and the last
+/// answer emphasized after an example is taken as its answer for that part. Part 2 often refers
+/// back to the last example of part 1, so its answers go there until it shows an example of its
+/// own. This is a guess, so the fixtures are worth a look before they are committed.
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let articles = page.split("<article").skip(1);
+    for (part, article) in (1..=2).zip(articles) {
+        let article = article.split("</article>").next().unwrap_or_default();
+        for item in items(article) {
+            match item {
+                Item::Example(input) => examples.push(Example {
+                    number: examples.len() as u32 + 1,
+                    input,
+                    answers: BTreeMap::new(),
+                }),
+                Item::Answer(answer) => {
+                    if let Some(example) = examples.last_mut() {
+                        example.answers.insert(part, answer);
+                    }
+                }
+            }
+        }
+    }
+    examples
+}
+
+/// The example numbered by the name of a `K.txt` file
+fn example_number(path: &Path) -> Option<u32> {
+    path.file_name()?
+        .to_str()?
+        .strip_suffix(".txt")?
+        .parse()
+        .ok()
+}
+
+/// Load the examples of a day, which has none when it has no fixtures
+pub fn load(root: &Path, year: u32, day: u32) -> Result<Vec<Example>, String> {
+    let dir = examples_dir(root, year, day);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("{}: {e}", dir.display())),
+    };
+    let mut examples = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| format!("{}: {e}", dir.display()))?.path();
+        let Some(number) = example_number(&path) else {
+            continue;
+        };
+        examples.push(Example {
+            number,
+            input: fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?,
+            answers: BTreeMap::new(),
+        });
+    }
+    examples.sort_unstable_by_key(|e| e.number);
+
+    let path = dir.join("answers.txt");
+    let answers = match fs::read_to_string(&path) {
+        Ok(answers) => answers,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    for (n, line) in answers.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = || format!("{}: line {n}: expected EXAMPLE PART ANSWER", path.display());
+        let mut words = line.splitn(3, ' ').map(str::trim);
+        let (Some(number), Some(part), Some(answer)) = (words.next(), words.next(), words.next())
+        else {
+            return Err(error());
+        };
+        let (Ok(number), Ok(part)) = (number.parse::<u32>(), part.parse()) else {
+            return Err(error());
+        };
+        let example = examples
+            .iter_mut()
+            .find(|e| e.number == number)
+            .ok_or_else(|| format!("{}: line {n}: no example {number}", path.display()))?;
+        example.answers.insert(part, answer.to_string());
+    }
+    Ok(examples)
+}
+
+/// Save the examples of a day, unless it has some already, returning the paths that were written
+pub fn save(
+    root: &Path,
+    year: u32,
+    day: u32,
+    examples: &[Example],
+) -> Result<Vec<PathBuf>, String> {
+    let dir = examples_dir(root, year, day);
+    if !load(root, year, day)?.is_empty() {
+        return Err(format!("{} has examples already", dir.display()));
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+
+    let mut written = vec![];
+    let mut answers = String::from("# EXAMPLE PART ANSWER\n");
+    for example in examples {
+        let path = dir.join(format!("{}.txt", example.number));
+        let mut input = example.input.clone();
+        if !input.ends_with('\n') {
+            input.push('\n');
+        }
+        fs::write(&path, input).map_err(|e| format!("{}: {e}", path.display()))?;
+        written.push(path);
+        for (part, answer) in &example.answers {
+            writeln!(answers, "{} {part} {answer}", example.number).unwrap();
+        }
+    }
+    if examples.iter().any(|e| !e.answers.is_empty()) {
+        let path = dir.join("answers.txt");
+        fs::write(&path, answers).map_err(|e| format!("{}: {e}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Handy Haversacks ---</h2>
+<p>For example, consider the following rules:</p>
+<pre><code>light red bags contain 1 bright white bag, 2 muted yellow bags.
+faded blue bags contain no other bags.
+</code></pre>
+<p>The number of bag colors is <code><em>2</em></code> &amp; not <code>3</code>.
+So <code><em>4</em></code> bag colors can eventually contain a <em>shiny gold</em> bag.</p>
+</article>
+<p>Your puzzle answer was <code>229</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, a single shiny gold bag must contain <code><em>32</em></code> bags.</p>
+<pre><code>shiny gold bags contain 2 dark red bags.
+dark red bags contain no &lt;other&gt; bags.
+</code></pre>
+<p>In this example, a single shiny gold bag must contain <code><em>126</em></code> other bags.</p>
+</article>
+</main>"#;
+
+    fn example(number: u32, input: &str, answers: &[(u32, &str)]) -> Example {
+        Example {
+            number,
+            input: input.to_string(),
+            answers: answers.iter().map(|(p, a)| (*p, a.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            decode("a &lt;b&gt; &amp;&#39;c&#39; & d;"),
+            "a <b> &'c' & d;"
+        );
+    }
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                example(
+                    1,
+                    "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+                     faded blue bags contain no other bags.\n",
+                    &[(1, "4"), (2, "32")]
+                ),
+                example(
+                    2,
+                    "shiny gold bags contain 2 dark red bags.\n\
+                     dark red bags contain no <other> bags.\n",
+                    &[(2, "126")]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let root = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let examples = vec![
+            example(1, "a\nb", &[(1, "4"), (2, "32")]),
+            example(2, "c\n", &[]),
+        ];
+
+        let written = save(&root, 2020, 7, &examples).unwrap();
+        assert_eq!(written.len(), 3);
+        let dir = examples_dir(&root, 2020, 7);
+        assert_eq!(fs::read_to_string(dir.join("1.txt")).unwrap(), "a\nb\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("answers.txt")).unwrap(),
+            "# EXAMPLE PART ANSWER\n1 1 4\n1 2 32\n"
+        );
+
+        let loaded = load(&root, 2020, 7).unwrap();
+        assert_eq!(loaded[0], example(1, "a\nb\n", &[(1, "4"), (2, "32")]));
+        assert_eq!(loaded[1], examples[1]);
+        assert!(save(&root, 2020, 7, &examples).is_err());
+        assert_eq!(load(&root, 2020, 8), Ok(vec![]));
+
+        fs::write(dir.join("answers.txt"), "3 1 5\n").unwrap();
+        assert!(load(&root, 2020, 7)
+            .unwrap_err()
+            .ends_with("line 1: no example 3"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
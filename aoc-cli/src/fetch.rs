@@ -0,0 +1,189 @@
+//! Download puzzle inputs from adventofcode.com into `aocYYYY/input/YYYY/dayN.txt`.
+//!
+//! Inputs are personal, so the requests are authenticated with the `session` cookie of a logged in
+//! browser, taken from the `AOC_SESSION` variable. An input that was saved already is never
+//! downloaded again, and requests are spaced out so that fetching a whole year does not hammer the
+//! server.
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::input_path;
+
+/// The variable holding the session cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the requests as coming from this repository, as the site asks of automated tools
+const USER_AGENT: &str = "github.com/avrahamappel/aoc aoc-cli";
+
+/// The year of the first event
+pub const FIRST_YEAR: u32 = 2015;
+
+/// The year of a day counted from 1970-01-01, in the proleptic Gregorian calendar
+fn year_of(days: u64) -> u32 {
+    // Shift to 0000-03-01 so that leap days end each 400 year era, and count from there
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Days from March 1st, so that January and February belong to the next year
+    let year = era * 400 + year_of_era + u64::from(day_of_year >= 306);
+    u32::try_from(year).expect("The year fits in a u32")
+}
+
+/// The years that have an event, from the first one up to the current year in UTC
+pub fn events() -> RangeInclusive<u32> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The clock is past 1970");
+    FIRST_YEAR..=year_of(now.as_secs() / 86_400)
+}
+
+/// Check that `year` has an event, whether or not its solutions are compiled in
+pub fn check_year(year: u32) -> Result<(), String> {
+    let events = events();
+    if events.contains(&year) {
+        Ok(())
+    } else {
+        Err(format!(
+            "There is no event in {year}, only from {} to {}",
+            events.start(),
+            events.end()
+        ))
+    }
+}
+
+/// The session cookie from the environment
+pub fn session_from_env() -> Result<String, String> {
+    std::env::var(SESSION_VAR)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            format!("Set {SESSION_VAR} to the session cookie of a logged in adventofcode.com")
+        })
+}
+
+/// Whether an input came from the cache or from the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// An authenticated client of the site, waiting at least `min_interval` between two requests
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            min_interval: Duration::from_secs(3),
+            last_request: None,
+        }
+    }
+
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Wait until the next request is allowed
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            if let Some(wait) = self.min_interval.checked_sub(last.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    /// Get `path` on the site, or post `form` to it, and return the body of the response
+    pub(crate) fn request(
+        &mut self,
+        path: &str,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, String> {
+        self.throttle();
+        let url = format!("{}{path}", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let response = match form {
+            Some(form) => self.agent.post(&url).set("Cookie", &cookie).send_form(form),
+            None => self.agent.get(&url).set("Cookie", &cookie).call(),
+        };
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| format!("{url}: could not read the response: {e}")),
+            Err(ureq::Error::Status(404, _)) => Err(format!("{url}: not found, is it unlocked?")),
+            Err(ureq::Error::Status(400 | 500, _)) => Err(format!(
+                "{url}: rejected, the session in {SESSION_VAR} may have expired"
+            )),
+            Err(ureq::Error::Status(status, _)) => Err(format!("{url}: status {status}")),
+            Err(e) => Err(format!("{url}: {e}")),
+        }
+    }
+
+    /// Save the input of a day under `root`, unless it was saved already
+    pub fn fetch_input(&mut self, root: &Path, year: u32, day: u32) -> Result<Fetched, String> {
+        let path = input_path(root, year, day);
+        // `aoc new` leaves an empty file to fill in, which does not count
+        if fs::read_to_string(&path).is_ok_and(|input| !input.trim().is_empty()) {
+            return Ok(Fetched::Cached);
+        }
+
+        let input = self.request(&format!("/{year}/day/{day}/input"), None)?;
+        if input.trim().is_empty() {
+            return Err(format!("The input of {year} day {day} is empty"));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        fs::write(&path, input).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_of() {
+        for (days, year) in [
+            (0, 1970),
+            (364, 1970),
+            (365, 1971),
+            // 2015-12-01, 2016-02-29, 2016-12-31 and 2017-01-01
+            (16_770, 2015),
+            (16_860, 2016),
+            (17_166, 2016),
+            (17_167, 2017),
+            // 2100-03-01, after a century without a leap day
+            (47_541, 2100),
+        ] {
+            assert_eq!(year_of(days), year, "{days}");
+        }
+        assert!(check_year(2015).is_ok());
+        assert!(check_year(2014).is_err());
+        assert!(check_year(events().end() + 1).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Run the solutions of every year from a single binary
+
+pub mod answers;
+pub mod attribute;
+pub mod bench;
+pub mod check;
+pub mod examples;
+pub mod fetch;
+pub mod pool;
+pub mod progress;
+pub mod run;
+pub mod scaffold;
+pub mod solutions;
+pub mod submit;
+pub mod table;
+
+use std::path::{Path, PathBuf};
+
+/// The root of the repository, where the year crates live
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner should live in the repository")
+        .to_path_buf()
+}
+
+/// The path of the puzzle input for a day, `aocYYYY/input/YYYY/dayN.txt`
+pub fn input_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(format!("aoc{year}/input/{year}/day{day}.txt"))
+}
+
+/// The path of the accepted answers of a year, `aocYYYY/answers.txt`
+pub fn answers_path(root: &Path, year: u32) -> PathBuf {
+    root.join(format!("aoc{year}/answers.txt"))
+}
+
+/// The directory of the saved puzzle examples of a day, `aocYYYY/examples/dayN`
+pub fn examples_dir(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(format!("aoc{year}/examples/day{day}"))
+}
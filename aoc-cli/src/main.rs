@@ -0,0 +1,599 @@
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_cli::answers::Answers;
+use aoc_cli::bench::{self, change, current_commit, history_path, Entry, History};
+use aoc_cli::check::{cross_check, inputs, variants};
+use aoc_cli::examples::{self, extract, Example};
+use aoc_cli::fetch::{check_year, session_from_env, Client, Fetched, BASE_URL};
+use aoc_cli::pool;
+use aoc_cli::progress::{self, list, ranges, Progress, Status};
+use aoc_cli::run::{read_input, run_from_disk, Outcome, DEFAULT_TIMEOUT};
+use aoc_cli::scaffold::scaffold;
+use aoc_cli::solutions::{Selection, YEARS};
+use aoc_cli::submit::{attempts_path, Attempts, Submission, Verdict};
+use aoc_cli::table::Table;
+
+const USAGE: &str = "\
+Usage: aoc [run] [-j N] [--timeout SECONDS] [YEAR [DAY [PART]]]
+       aoc bench [--warmup N] [--samples N] [--threshold PERCENT] [--timeout SECONDS]
+                 [YEAR [DAY [PART]]]
+       aoc check [-j N] [--timeout SECONDS] [YEAR [DAY [PART]]]
+       aoc new [--page PAGE | --example FILE --answer ANSWER] YEAR DAY
+       aoc examples YEAR DAY PAGE
+       aoc fetch [YEAR [DAY]]
+       aoc submit [--timeout SECONDS] YEAR DAY PART [ANSWER]
+       aoc progress [--markdown] [YEAR]
+
+run    Run the selected solutions against their inputs under aocYYYY/input/YYYY/dayN.txt
+       and print the answers and timings. Without a year, every year is run. The solutions
+       run on N threads (-j or --jobs, one per core by default), and are timed in CPU time
+       so that they do not skew each other's timings.
+bench  Time the generator and the solver of the selected solutions over several samples
+       (10 by default) after a few warm-up runs (2 by default). The medians are recorded in
+       aoc-cli/bench-history.tsv under the current git commit, and the solutions that got
+       slower than the latest other commit by more than the threshold (10% by default)
+       are reported as regressions. Solutions are benched one at a time.
+check  Run every variant of the selected parts that have several, on the examples under
+       aocYYYY/examples/dayN/K.txt and on the input, and report the variants that disagree.
+       Variants with their own answer in aocYYYY/answers.txt are known to disagree and do
+       not fail the check. Parts are checked on N threads, like run.
+new    Start a day in the crate of the year: src/dayN.rs with todo!() parts, its mod line
+       in lib.rs and an empty input file. The examples of the puzzle page saved from the
+       browser, or a single example (FILE) with its part 1 answer, are saved under
+       aocYYYY/examples/dayN and the example tests check them. Use - to read from stdin.
+examples
+       Save the examples of a puzzle page and the answers it gives for them under
+       aocYYYY/examples/dayN, where the runner's tests check every solution against them.
+fetch  Download the inputs of the selected days from adventofcode.com, using the session
+       cookie in AOC_SESSION. Inputs that were saved already are left alone.
+submit Submit an answer, or the answer of the solution to its input, to adventofcode.com.
+       Every verdict is recorded in aoc-cli/submissions.tsv, and answers that were judged
+       already, or are beyond an answer that was too high or too low, are not submitted.
+progress
+       Count the stars of each year, from the #[aoc] solutions in the year crates and the
+       answers in aocYYYY/answers.txt, and list the days without a solution and the parts
+       (as DAY.PART) that are missing, still todo!() or have no recorded answer. Years that
+       are not compiled into the runner are counted too.
+
+Each part is given up on after 60 seconds, or the --timeout in seconds, where 0 means no limit.
+Long loops can call aoc_common::cancel::checkpoint() to stop as soon as they time out.
+
+Set AOC_LOG to see what the solutions log, e.g. AOC_LOG=debug or AOC_LOG=aoc2016::day10=trace.";
+
+/// Remove `--name VALUE` from the arguments and parse its value
+fn take_option<T: std::str::FromStr>(
+    args: &mut Vec<String>,
+    name: &str,
+) -> Result<Option<T>, String> {
+    let Some(i) = args.iter().position(|a| a == name) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err(format!("Missing value for {name}"));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid value [{value}] for {name}"))
+}
+
+/// Remove `--timeout SECONDS` from the arguments, where 0 means no limit
+fn take_timeout(args: &mut Vec<String>) -> Result<Option<Duration>, String> {
+    let Some(secs) = take_option::<f64>(args, "--timeout")? else {
+        return Ok(Some(DEFAULT_TIMEOUT));
+    };
+    let timeout = Duration::try_from_secs_f64(secs)
+        .map_err(|_| format!("Invalid value [{secs}] for --timeout"))?;
+    Ok((!timeout.is_zero()).then_some(timeout))
+}
+
+/// Parse the time limit, the number of threads and the selection of `aoc run` and `aoc check`
+fn parse_run_args(mut args: Vec<String>) -> Result<(Selection, Option<Duration>, usize), String> {
+    let timeout = take_timeout(&mut args)?;
+    let jobs = match take_option(&mut args, "-j")? {
+        Some(jobs) => Some(jobs),
+        None => take_option(&mut args, "--jobs")?,
+    };
+    let jobs = jobs.unwrap_or_else(pool::default_jobs).max(1);
+    Ok((parse_selection(&args)?, timeout, jobs))
+}
+
+/// Parse the optional year, day and part arguments
+/// Parse a year, a day and a part, whether or not the year is compiled into the runner
+fn parse_numbers(args: &[String]) -> Result<Selection, String> {
+    let mut numbers = args.iter().map(|a| {
+        a.parse::<u32>()
+            .map_err(|_| format!("Invalid argument [{a}]"))
+    });
+    let selection = Selection {
+        year: numbers.next().transpose()?,
+        day: numbers.next().transpose()?,
+        part: numbers.next().transpose()?,
+    };
+    if numbers.next().is_some() {
+        return Err(String::from("Too many arguments"));
+    }
+    Ok(selection)
+}
+
+fn parse_selection(args: &[String]) -> Result<Selection, String> {
+    let selection = parse_numbers(args)?;
+    if let Some(year) = selection.year {
+        if !YEARS.contains(&year) {
+            return Err(format!("Year {year} is not compiled into the runner"));
+        }
+    }
+    Ok(selection)
+}
+
+fn run(selection: Selection, timeout: Option<Duration>, jobs: usize) -> ExitCode {
+    // Panics are reported in the table instead
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let root = aoc_cli::root();
+    let mut table = Table::new([
+        "Year",
+        "Day",
+        "Part",
+        "Variant",
+        "Answer",
+        "Generator",
+        "Runner",
+    ]);
+    let mut total = Duration::ZERO;
+    let mut failed = false;
+
+    let start = Instant::now();
+    let solutions: Vec<_> = selection.solutions().collect();
+    let reports = pool::map(&solutions, jobs, |solution| {
+        run_from_disk(&root, solution, timeout)
+    });
+    let elapsed = start.elapsed();
+
+    for report in reports {
+        let solution = report.solution;
+        failed |= matches!(report.outcome, Outcome::Failed(_) | Outcome::TimedOut(_));
+        total += report.generator + report.runner;
+        table.push([
+            solution.year.to_string(),
+            solution.day.to_string(),
+            solution.part.to_string(),
+            solution.name.unwrap_or_default().to_string(),
+            report.outcome.to_string(),
+            format!("{:?}", report.generator),
+            format!("{:?}", report.runner),
+        ]);
+    }
+
+    print!("{table}");
+    println!("Total: {total:?} of CPU time, {elapsed:?} elapsed with -j {jobs}");
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(selection: Selection, options: bench::Options, threshold: f64) -> Result<bool, String> {
+    // Panics are reported in the table instead
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let root = aoc_cli::root();
+    let commit = current_commit(&root)?;
+    let path = history_path(&root);
+    let mut history = History::load(&path)?;
+
+    let mut table = Table::new([
+        "Year",
+        "Day",
+        "Part",
+        "Variant",
+        "Generator",
+        "Runner",
+        "Previous",
+        "Change",
+    ]);
+    let mut ok = true;
+
+    for solution in selection.solutions() {
+        let mut row = vec![
+            solution.year.to_string(),
+            solution.day.to_string(),
+            solution.part.to_string(),
+            solution.name.unwrap_or_default().to_string(),
+        ];
+        let timing = match read_input(&root, solution) {
+            Some(input) => bench::bench(solution, &input, options),
+            None => Err(Outcome::MissingInput),
+        };
+        match timing {
+            Ok(timing) => {
+                row.push(format!("{:?}", timing.generator));
+                row.push(format!("{:?}", timing.runner));
+                if let Some(previous) = history.previous(&commit, solution) {
+                    let change = change(previous.timing, timing);
+                    row.push(format!("{:?}", previous.timing.total()));
+                    if change > threshold {
+                        ok = false;
+                        row.push(format!("{:+.1}% REGRESSION", change * 100.0));
+                    } else {
+                        row.push(format!("{:+.1}%", change * 100.0));
+                    }
+                }
+                history.record(Entry::new(&commit, solution, timing));
+            }
+            Err(outcome) => {
+                ok &= outcome == Outcome::MissingInput;
+                row.push(outcome.to_string());
+            }
+        }
+        table.push(row);
+    }
+
+    print!("{table}");
+    history
+        .save(&path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    println!("Recorded under {commit} in {}", path.display());
+    Ok(ok)
+}
+
+fn check(selection: Selection, timeout: Option<Duration>, jobs: usize) -> Result<bool, String> {
+    // Panics are reported as disagreements instead
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let root = aoc_cli::root();
+    let mut table = Table::new([
+        "Year", "Day", "Part", "Input", "Variant", "Answer", "Status",
+    ]);
+    let mut ok = true;
+    let mut checked = 0;
+
+    let parts = variants(selection);
+    let checks = pool::map(&parts, jobs, |part| {
+        let first = part[0];
+        let answers = Answers::load(&root, first.year)?;
+        let inputs = inputs(&root, first)?;
+        Ok::<_, String>((inputs.len(), cross_check(part, &inputs, &answers, timeout)))
+    });
+    for check in checks {
+        let (inputs, disagreements) = check?;
+        checked += inputs;
+        for disagreement in disagreements {
+            ok &= disagreement.known;
+            for (solution, outcome) in &disagreement.outcomes {
+                table.push([
+                    solution.year.to_string(),
+                    solution.day.to_string(),
+                    solution.part.to_string(),
+                    disagreement.input.clone(),
+                    solution.name.unwrap_or_default().to_string(),
+                    outcome.to_string(),
+                    String::from(if disagreement.known {
+                        "known"
+                    } else {
+                        "DISAGREE"
+                    }),
+                ]);
+            }
+        }
+    }
+
+    print!("{table}");
+    println!("Checked {checked} inputs");
+    Ok(ok)
+}
+
+/// Parse the options and the selection of `aoc bench`, with the threshold as a fraction
+fn parse_bench_args(mut args: Vec<String>) -> Result<(Selection, bench::Options, f64), String> {
+    let defaults = bench::Options::default();
+    let options = bench::Options {
+        warmup: take_option(&mut args, "--warmup")?.unwrap_or(defaults.warmup),
+        samples: take_option(&mut args, "--samples")?
+            .unwrap_or(defaults.samples)
+            .max(1),
+        timeout: take_timeout(&mut args)?,
+    };
+    let threshold: f64 = take_option(&mut args, "--threshold")?.unwrap_or(10.0);
+    Ok((parse_selection(&args)?, options, threshold / 100.0))
+}
+
+/// Read a file given as an argument, or stdin for `-`
+fn read_file(path: &str) -> Result<String, String> {
+    if path == "-" {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    }
+    .map_err(|e| format!("{path}: {e}"))
+}
+
+/// Parse a year and a day
+fn parse_day(args: &[String]) -> Result<(u32, u32), String> {
+    let [year, day] = args else {
+        return Err(String::from("Expected a year and a day"));
+    };
+    let number = |a: &String| {
+        a.parse::<u32>()
+            .map_err(|_| format!("Invalid argument [{a}]"))
+    };
+    Ok((number(year)?, number(day)?))
+}
+
+/// Parse the arguments of `aoc new`
+fn parse_new_args(mut args: Vec<String>) -> Result<(u32, u32, Vec<Example>), String> {
+    let page: Option<String> = take_option(&mut args, "--page")?;
+    let example: Option<String> = take_option(&mut args, "--example")?;
+    let answer: Option<String> = take_option(&mut args, "--answer")?;
+    let examples = match (page, example, answer) {
+        (Some(page), None, None) => extract(&read_file(&page)?),
+        (None, Some(path), Some(answer)) => vec![Example {
+            number: 1,
+            input: read_file(&path)?,
+            answers: BTreeMap::from([(1, answer)]),
+        }],
+        (None, None, None) => vec![],
+        _ => {
+            return Err(String::from(
+                "Give either --page, or --example and --answer together",
+            ))
+        }
+    };
+    let (year, day) = parse_day(&args)?;
+    Ok((year, day, examples))
+}
+
+fn new(year: u32, day: u32, examples: Vec<Example>) -> Result<bool, String> {
+    for path in scaffold(&aoc_cli::root(), year, day, &examples)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(true)
+}
+
+/// Parse the arguments of `aoc examples`
+fn parse_examples_args(args: &[String]) -> Result<(u32, u32, String), String> {
+    let [year, day, page] = args else {
+        return Err(String::from(
+            "Expected a year, a day and a saved puzzle page",
+        ));
+    };
+    let (year, day) = parse_day(&[year.clone(), day.clone()])?;
+    Ok((year, day, page.clone()))
+}
+
+fn save_examples(year: u32, day: u32, page: &str) -> Result<bool, String> {
+    let examples = extract(&read_file(page)?);
+    if examples.is_empty() {
+        return Err(format!("{page} has no examples"));
+    }
+    let written = examples::save(&aoc_cli::root(), year, day, &examples)?;
+    for example in &examples {
+        let answers: Vec<_> = example
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part {part}: {answer}"))
+            .collect();
+        println!(
+            "Example {}: {} lines, {}",
+            example.number,
+            example.input.lines().count(),
+            if answers.is_empty() {
+                String::from("no answer")
+            } else {
+                answers.join(", ")
+            }
+        );
+    }
+    for path in written {
+        println!("Wrote {}", path.display());
+    }
+    Ok(true)
+}
+
+fn fetch(selection: Selection) -> Result<bool, String> {
+    if selection.part.is_some() {
+        return Err(String::from("Inputs are fetched by day"));
+    }
+    if let Some(year) = selection.year {
+        check_year(year)?;
+    }
+    // A day that was just created is not compiled in yet, but can still be fetched
+    let mut days = match selection {
+        Selection {
+            year: Some(year),
+            day: Some(day),
+            ..
+        } => vec![(year, day)],
+        _ => selection.solutions().map(|s| (s.year, s.day)).collect(),
+    };
+    days.dedup();
+    if days.is_empty() {
+        return Err(String::from(
+            "No solutions are compiled in for this selection, give the day to fetch",
+        ));
+    }
+
+    let root = aoc_cli::root();
+    let mut client = Client::new(BASE_URL, &session_from_env()?);
+    for (year, day) in days {
+        let fetched = match client.fetch_input(&root, year, day)? {
+            Fetched::Cached => "cached",
+            Fetched::Downloaded => "downloaded",
+        };
+        println!("{year} day {day}: {fetched}");
+    }
+    Ok(true)
+}
+
+/// Parse the arguments of `aoc submit`
+fn parse_submit_args(args: &[String]) -> Result<([u32; 3], Option<String>), String> {
+    let (numbers, answer) = match args {
+        [year, day, part] => ([year, day, part], None),
+        [year, day, part, answer] => ([year, day, part], Some(answer.clone())),
+        _ => {
+            return Err(String::from(
+                "Expected a year, a day, a part and maybe an answer",
+            ))
+        }
+    };
+    let [year, day, part] = numbers.map(|a| {
+        a.parse::<u32>()
+            .map_err(|_| format!("Invalid argument [{a}]"))
+    });
+    Ok(([year?, day?, part?], answer))
+}
+
+fn submit(
+    [year, day, part]: [u32; 3],
+    answer: Option<String>,
+    timeout: Option<Duration>,
+) -> Result<bool, String> {
+    let root = aoc_cli::root();
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let selection = Selection {
+                year: Some(year),
+                day: Some(day),
+                part: Some(part),
+            };
+            let solution = selection
+                .solutions()
+                .next()
+                .ok_or_else(|| format!("There is no solution to {year} day {day} part {part}"))?;
+            match run_from_disk(&root, solution, timeout).outcome {
+                Outcome::Answer(answer) => answer,
+                outcome => return Err(format!("{solution}: {outcome}")),
+            }
+        }
+    };
+
+    let path = attempts_path(&root);
+    let mut attempts = Attempts::load(&path)?;
+    let mut client = Client::new(BASE_URL, &session_from_env()?);
+    let submission = client.submit(&mut attempts, year, day, part, &answer)?;
+    attempts
+        .save(&path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let (verdict, known) = match submission {
+        Submission::Known(verdict) => (verdict, " (known from a previous attempt)"),
+        Submission::Judged(verdict) => (verdict, ""),
+    };
+    println!("{year} day {day} part {part}: {answer} is {verdict}{known}");
+    Ok(verdict == Verdict::Correct)
+}
+
+/// Parse the arguments of `aoc progress`
+fn parse_progress_args(mut args: Vec<String>) -> Result<(bool, Option<u32>), String> {
+    let markdown = match args.iter().position(|a| a == "--markdown") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let year = match &args[..] {
+        [] => None,
+        [year] => Some(
+            year.parse()
+                .map_err(|_| format!("Invalid argument [{year}]"))?,
+        ),
+        _ => return Err(String::from("Too many arguments")),
+    };
+    Ok((markdown, year))
+}
+
+fn progress(markdown: bool, year: Option<u32>) -> Result<bool, String> {
+    let root = aoc_cli::root();
+    let years = match year {
+        Some(year) => vec![year],
+        None => progress::years(&root)?,
+    };
+
+    let mut table = Table::new([
+        "Year",
+        "Stars",
+        "Missing days",
+        "Missing parts",
+        "Todo",
+        "Untested",
+    ]);
+    let (mut stars, mut max_stars) = (0, 0);
+    for year in years {
+        let progress = Progress::load(&root, year)?;
+        stars += progress.stars();
+        max_stars += progress.max_stars();
+        table.push([
+            year.to_string(),
+            format!("{}/{}", progress.stars(), progress.max_stars()),
+            ranges(&progress.missing_days()),
+            list(&progress.parts(Status::Missing)),
+            list(&progress.parts(Status::Todo)),
+            list(&progress.parts(Status::Untested)),
+        ]);
+    }
+
+    if markdown {
+        print!("{}", table.markdown());
+    } else {
+        print!("{table}");
+    }
+    println!("Total: {stars}/{max_stars} stars");
+    Ok(true)
+}
+
+/// Exit with a failure when a command failed or found a problem
+fn exit_code(result: Result<bool, String>) -> ExitCode {
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    aoc_common::logging::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let parsed = match args.first().map(String::as_str) {
+        Some("-h" | "--help" | "help") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some("bench") => parse_bench_args(args[1..].to_vec())
+            .map(|(selection, options, threshold)| exit_code(bench(selection, options, threshold))),
+        Some("check") => parse_run_args(args[1..].to_vec())
+            .map(|(selection, timeout, jobs)| exit_code(check(selection, timeout, jobs))),
+        Some("new") => parse_new_args(args[1..].to_vec())
+            .map(|(year, day, example)| exit_code(new(year, day, example))),
+        Some("examples") => parse_examples_args(&args[1..])
+            .map(|(year, day, page)| exit_code(save_examples(year, day, &page))),
+        Some("fetch") => parse_numbers(&args[1..]).map(|selection| exit_code(fetch(selection))),
+        Some("submit") => {
+            let mut args = args[1..].to_vec();
+            take_timeout(&mut args).and_then(|timeout| {
+                parse_submit_args(&args)
+                    .map(|(part, answer)| exit_code(submit(part, answer, timeout)))
+            })
+        }
+        Some("progress") => parse_progress_args(args[1..].to_vec())
+            .map(|(markdown, year)| exit_code(progress(markdown, year))),
+        Some("run") => parse_run_args(args[1..].to_vec())
+            .map(|(selection, timeout, jobs)| run(selection, timeout, jobs)),
+        _ => parse_run_args(args).map(|(selection, timeout, jobs)| run(selection, timeout, jobs)),
+    };
+
+    parsed.unwrap_or_else(|e| {
+        eprintln!("{e}\n\n{USAGE}");
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,68 @@
+//! Run independent tasks on a fixed number of threads, keeping their results in order
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// The number of threads to use by default, one per core
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Apply `f` to every item on up to `jobs` threads, which take the next item as soon as they are
+/// done with one, and return the results in the order of the items
+pub fn map<T, R>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let work = || {
+        let mut results = vec![];
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(i) else {
+                return results;
+            };
+            results.push((i, f(item)));
+        }
+    };
+
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let mut results: Vec<(usize, R)> = if jobs == 1 {
+        work()
+    } else {
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..jobs).map(|_| scope.spawn(work)).collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        })
+    };
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..20).collect();
+        // Later items finish first, and still come out in order
+        let squares = map(&items, 4, |n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(map(&items, 1, |n| n + 1)[19], 20);
+        assert_eq!(map(&[] as &[u64], 0, |n| *n), vec![]);
+    }
+}
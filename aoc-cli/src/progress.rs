@@ -0,0 +1,347 @@
+//! How far along each year is, from the solutions registered in the sources of the year crates and
+//! the answers recorded in `aocYYYY/answers.txt`.
+//!
+//! The sources are scanned rather than the solutions compiled into the runner, so that years that
+//! are not compiled in are counted too. A part is solved when it has an implemented solution and an
+//! accepted answer, which is what earns its star on the site.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use crate::answers::Answers;
+use crate::attribute::parse_attribute;
+
+/// A solution registered with `#[aoc(dayN, partM)]` in the source of a year crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registered {
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<String>,
+    /// Whether its body still has a `todo!()`
+    pub todo: bool,
+}
+
+/// The body of the first function in `source`, between its braces
+fn body(source: &str) -> Option<&str> {
+    let start = source.find("fn ")?;
+    let open = start + source[start..].find('{')?;
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The solutions registered in the source of a module
+pub fn registered(source: &str) -> Vec<Registered> {
+    let mut solutions = vec![];
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        offset += line.len();
+        if let Some((day, part, name)) = parse_attribute(line) {
+            solutions.push(Registered {
+                day,
+                part,
+                name,
+                todo: body(&source[offset..]).is_some_and(|b| b.contains("todo!(")),
+            });
+        }
+    }
+    solutions
+}
+
+/// The years that have a crate under `root`, in order
+pub fn years(root: &Path) -> Result<Vec<u32>, String> {
+    let mut years: Vec<u32> = fs::read_dir(root)
+        .map_err(|e| format!("{}: {e}", root.display()))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            path.join("src/lib.rs").exists().then_some(())?;
+            path.file_name()?
+                .to_str()?
+                .strip_prefix("aoc")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// The solutions registered in the crate of a year
+pub fn scan(root: &Path, year: u32) -> Result<Vec<Registered>, String> {
+    let src = root.join(format!("aoc{year}/src"));
+    let mut solutions = vec![];
+    for entry in fs::read_dir(&src).map_err(|e| format!("{}: {e}", src.display()))? {
+        let path = entry.map_err(|e| format!("{}: {e}", src.display()))?.path();
+        if path.extension().is_none_or(|e| e != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        solutions.extend(registered(&source));
+    }
+    Ok(solutions)
+}
+
+/// The number of days of a year, which went from 25 down to 12 in 2025
+pub fn last_day(year: u32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Where a part stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    /// No solution is registered
+    Missing,
+    /// Every solution is a `todo!()`
+    Todo,
+    /// A solution is implemented, but no answer was recorded for it
+    Untested,
+    Solved,
+}
+
+/// A day and part, shown as `9.2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Part {
+    pub day: u32,
+    pub part: u32,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.day, self.part)
+    }
+}
+
+/// The status of every part of a year. The last day has a single puzzle, and its second star is
+/// given for all the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub year: u32,
+    parts: BTreeMap<Part, Status>,
+}
+
+impl Progress {
+    pub fn new(year: u32, registered: &[Registered], answers: &Answers) -> Self {
+        let last_day = last_day(year);
+        let mut parts = BTreeMap::new();
+        for day in 1..=last_day {
+            for part in 1..=if day == last_day { 1 } else { 2 } {
+                let solutions: Vec<_> = registered
+                    .iter()
+                    .filter(|r| (r.day, r.part) == (day, part))
+                    .collect();
+                // The answer of a variant known to disagree does not tell whether the part is solved
+                let answered = answers
+                    .iter()
+                    .any(|(k, _)| (k.day, k.part) == (day, part) && k.variant.is_none());
+                let status = if solutions.is_empty() {
+                    Status::Missing
+                } else if solutions.iter().all(|r| r.todo) {
+                    Status::Todo
+                } else if answered {
+                    Status::Solved
+                } else {
+                    Status::Untested
+                };
+                parts.insert(Part { day, part }, status);
+            }
+        }
+        Self { year, parts }
+    }
+
+    /// Load the progress of a year from the crate and the answers under `root`
+    pub fn load(root: &Path, year: u32) -> Result<Self, String> {
+        Ok(Self::new(
+            year,
+            &scan(root, year)?,
+            &Answers::load(root, year)?,
+        ))
+    }
+
+    pub fn status(&self, day: u32, part: u32) -> Option<Status> {
+        self.parts.get(&Part { day, part }).copied()
+    }
+
+    /// The stars earned, out of twice the number of days
+    pub fn stars(&self) -> u32 {
+        let solved = self
+            .parts
+            .values()
+            .filter(|s| **s == Status::Solved)
+            .count() as u32;
+        if solved == self.parts.len() as u32 {
+            solved + 1
+        } else {
+            solved
+        }
+    }
+
+    pub fn max_stars(&self) -> u32 {
+        last_day(self.year) * 2
+    }
+
+    /// The days without any solution
+    pub fn missing_days(&self) -> Vec<u32> {
+        (1..=last_day(self.year))
+            .filter(|day| {
+                self.parts
+                    .iter()
+                    .filter(|(p, _)| p.day == *day)
+                    .all(|(_, s)| *s == Status::Missing)
+            })
+            .collect()
+    }
+
+    /// The parts with a status, leaving out the missing parts of missing days
+    pub fn parts(&self, status: Status) -> Vec<Part> {
+        let missing_days = self.missing_days();
+        self.parts
+            .iter()
+            .filter(|(p, s)| **s == status && !missing_days.contains(&p.day))
+            .map(|(p, _)| *p)
+            .collect()
+    }
+}
+
+/// Days as ranges, like `1-17, 22`
+pub fn ranges(days: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parts as a list, like `9.2, 20.1`
+pub fn list(parts: &[Part]) -> String {
+    parts
+        .iter()
+        .map(Part::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+#[aoc_generator(day9)]
+fn parse(input: &str) -> Vec<u32> {
+    input.lines().map(|l| l.parse().unwrap()).collect()
+}
+
+#[aoc(day9, part1)]
+fn part1(input: &[u32]) -> u32 {
+    if input.is_empty() {
+        return 0;
+    }
+    input.iter().sum()
+}
+
+#[aoc(day9, part2, Slow)]
+fn part2_slow(input: &[u32]) -> u32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn part2_example() {
+        todo!()
+    }
+}
+";
+
+    fn solution(day: u32, part: u32, todo: bool) -> Registered {
+        Registered {
+            day,
+            part,
+            name: None,
+            todo,
+        }
+    }
+
+    #[test]
+    fn test_registered() {
+        assert_eq!(
+            registered(SOURCE),
+            vec![
+                solution(9, 1, false),
+                Registered {
+                    name: Some(String::from("Slow")),
+                    ..solution(9, 2, true)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_progress() {
+        let registered = [
+            solution(1, 1, false),
+            solution(1, 2, false),
+            solution(2, 1, false),
+            solution(2, 2, true),
+            solution(4, 1, false),
+            solution(4, 2, true),
+            solution(4, 2, false),
+        ];
+        let answers: Answers = "1 1 3\n1 2 4\n2 1 5\n4 1/naive 6\n".parse().unwrap();
+        let progress = Progress::new(2015, &registered, &answers);
+
+        assert_eq!(progress.stars(), 3);
+        assert_eq!(progress.max_stars(), 50);
+        assert_eq!(progress.status(2, 2), Some(Status::Todo));
+        assert_eq!(progress.status(4, 2), Some(Status::Untested));
+        assert_eq!(progress.status(25, 2), None);
+        assert_eq!(ranges(&progress.missing_days()), "3, 5-25");
+        assert_eq!(list(&progress.parts(Status::Todo)), "2.2");
+        assert_eq!(list(&progress.parts(Status::Untested)), "4.1, 4.2");
+        assert_eq!(progress.parts(Status::Missing), vec![]);
+    }
+
+    #[test]
+    fn test_all_stars() {
+        let registered: Vec<_> = (1..=12)
+            .flat_map(|day| [solution(day, 1, false), solution(day, 2, false)])
+            .collect();
+        let answers: Answers = (1..=12)
+            .map(|day| format!("{day} 1 1\n{day} 2 2\n"))
+            .collect::<String>()
+            .parse()
+            .unwrap();
+        let progress = Progress::new(2025, &registered, &answers);
+        assert_eq!(progress.stars(), 24);
+        assert_eq!(progress.max_stars(), 24);
+    }
+}
@@ -0,0 +1,248 @@
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel::{self, Cancelled, Token};
+use aoc_runner::ArcStr;
+
+use crate::input_path;
+use crate::solutions::Solution;
+
+/// What came out of running a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// The generator or the solver returned an error or panicked
+    Failed(String),
+    MissingInput,
+    /// The solution did not finish within its time limit
+    TimedOut(Duration),
+}
+
+/// How long a part may run by default before it is given up on
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a solution that timed out is given to notice its cancellation before the next one
+/// starts
+const GRACE: Duration = Duration::from_secs(1);
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Answer(a) => write!(f, "{a}"),
+            Self::Failed(e) => write!(f, "FAILED: {e}"),
+            Self::MissingInput => write!(f, "missing input"),
+            Self::TimedOut(limit) => write!(f, "TIMED OUT after {limit:?}"),
+        }
+    }
+}
+
+/// The outcome of a solution along with how long each step took, in CPU time of the thread that
+/// ran it so that solutions running in parallel do not skew each other's timings
+pub struct Report {
+    pub solution: &'static Solution,
+    pub outcome: Outcome,
+    pub generator: Duration,
+    pub runner: Duration,
+}
+
+/// The CPU time used by the current thread so far
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid timespec to write to, and the clock exists on every unix
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    assert_eq!(result, 0, "The thread CPU clock should be readable");
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
+
+/// The time since the runner started, where there is no thread CPU clock to read
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Duration {
+    static START: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    START.get_or_init(Instant::now).elapsed()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if payload.is::<Cancelled>() {
+        String::from("cancelled")
+    } else {
+        String::from("panicked")
+    }
+}
+
+/// Run a solution against the given input, catching panics such as unfinished `todo!()` parts
+fn run_here(solution: &'static Solution, input: &str) -> Report {
+    let input = ArcStr::from(input);
+    let mut report = Report {
+        solution,
+        outcome: Outcome::MissingInput,
+        generator: Duration::ZERO,
+        runner: Duration::ZERO,
+    };
+
+    let start = thread_cpu_time();
+    let runner = panic::catch_unwind(AssertUnwindSafe(|| (solution.factory)(input)));
+    report.generator = thread_cpu_time() - start;
+    let runner = match runner {
+        Ok(Ok(runner)) => runner,
+        Ok(Err(e)) => {
+            report.outcome = Outcome::Failed(format!("generating: {e}"));
+            return report;
+        }
+        Err(payload) => {
+            report.outcome = Outcome::Failed(format!("generating: {}", panic_message(&*payload)));
+            return report;
+        }
+    };
+
+    let start = thread_cpu_time();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| runner.try_run()));
+    report.runner = thread_cpu_time() - start;
+    report.outcome = match answer {
+        Ok(Ok(answer)) => Outcome::Answer(answer.to_string()),
+        Ok(Err(e)) => Outcome::Failed(format!("running: {e}")),
+        Err(payload) => Outcome::Failed(format!("running: {}", panic_message(&*payload))),
+    };
+    report
+}
+
+/// Run a solution against the given input, giving up on it after `timeout`. The solution runs on
+/// a thread of its own, whose cancellation token is cancelled when it times out; a solution that
+/// never checks the token keeps running in the background until the runner exits.
+pub fn run(solution: &'static Solution, input: &str, timeout: Option<Duration>) -> Report {
+    let Some(timeout) = timeout else {
+        return run_here(solution, input);
+    };
+
+    let token = Token::new();
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let worker_token = token.clone();
+    let start = Instant::now();
+    let spawned = thread::Builder::new()
+        .name(solution.to_string())
+        // As much stack as the main thread, for the recursive solutions
+        .stack_size(8 << 20)
+        .spawn(move || {
+            let report = cancel::with_token(worker_token, || run_here(solution, &input));
+            // The runner stops listening when the solution times out
+            let _ = sender.send(report);
+        });
+    if let Err(e) = spawned {
+        return Report {
+            solution,
+            outcome: Outcome::Failed(format!("starting a thread: {e}")),
+            generator: Duration::ZERO,
+            runner: Duration::ZERO,
+        };
+    }
+
+    let outcome = match receiver.recv_timeout(timeout) {
+        Ok(report) => return report,
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            let _ = receiver.recv_timeout(GRACE);
+            Outcome::TimedOut(timeout)
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Outcome::Failed(String::from("the thread running the solution died"))
+        }
+    };
+    Report {
+        solution,
+        outcome,
+        generator: Duration::ZERO,
+        runner: start.elapsed(),
+    }
+}
+
+/// Read the puzzle input of a solution under `root`, if it has been saved
+pub fn read_input(root: &Path, solution: &Solution) -> Option<String> {
+    fs::read_to_string(input_path(root, solution.year, solution.day))
+        .ok()
+        .filter(|input| !input.trim().is_empty())
+}
+
+/// Run a solution against its puzzle input under `root`, giving up on it after `timeout`
+pub fn run_from_disk(
+    root: &Path,
+    solution: &'static Solution,
+    timeout: Option<Duration>,
+) -> Report {
+    match read_input(root, solution) {
+        Some(input) => run(solution, &input, timeout),
+        None => Report {
+            solution,
+            outcome: Outcome::MissingInput,
+            generator: Duration::ZERO,
+            runner: Duration::ZERO,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use aoc_runner::Runner;
+
+    use super::*;
+
+    /// A runner that only stops when it is cancelled
+    struct Spin;
+
+    impl Runner for Spin {
+        fn gen(_: ArcStr) -> Self {
+            Self
+        }
+
+        fn run(&self) -> Box<dyn Display> {
+            loop {
+                cancel::checkpoint();
+                thread::yield_now();
+            }
+        }
+
+        fn bench(&self, _: fn(&dyn Display)) {}
+    }
+
+    static SPIN: Solution = Solution {
+        year: 2015,
+        day: 20,
+        part: 1,
+        name: None,
+        factory: |input| Ok(Box::new(Spin::try_gen(input)?)),
+    };
+
+    #[cfg(unix)]
+    #[test]
+    fn test_thread_cpu_time() {
+        // Sleeping, like waiting for a core taken by another solution, takes no CPU time
+        let start = thread_cpu_time();
+        thread::sleep(Duration::from_millis(50));
+        assert!(thread_cpu_time() - start < Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limit = Duration::from_millis(50);
+        let report = run(&SPIN, "", Some(limit));
+        assert_eq!(report.outcome, Outcome::TimedOut(limit));
+        assert!(report.runner >= limit);
+        // The spinning thread noticed the cancellation well within the grace period
+        assert!(report.runner < limit + GRACE);
+    }
+}
@@ -0,0 +1,234 @@
+//! Start a new day in an existing year crate: `src/dayN.rs` from the usual template, its `mod`
+//! line in `lib.rs`, an empty input file to paste the puzzle input into, and optionally the
+//! examples of the puzzle, which the example tests then check.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::examples::{self, Example};
+use crate::input_path;
+
+/// The body of the example test of a part, which checks the first example with an answer for
+/// that part, or is a placeholder when there is none
+fn example_test(day: u32, part: u32, examples: &[Example]) -> String {
+    match examples
+        .iter()
+        .find_map(|e| Some((e.number, e.answers.get(&part)?)))
+    {
+        Some((number, answer)) => format!(
+            "        assert_eq!(
+            part{part}(&parse(include_str!(\"../examples/day{day}/{number}.txt\")).unwrap()),
+            {answer:?}
+        );"
+        ),
+        None => {
+            format!("        assert_eq!(part{part}(&parse(\"<EXAMPLE>\").unwrap()), \"<RESULT>\");")
+        }
+    }
+}
+
+/// The source of a new day, with the parts left as `todo!()`
+pub fn template(day: u32, examples: &[Example]) -> String {
+    let part1_example = example_test(day, 1, examples);
+    let part2_example = example_test(day, 2, examples);
+    format!(
+        "\
+use aoc_common::parse::{{ParseError, Source}};
+use aoc_runner_derive::{{aoc, aoc_generator}};
+
+#[aoc_generator(day{day})]
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+    let source = Source::new(input);
+    Ok(source.lines().map(String::from).collect())
+}}
+
+#[aoc(day{day}, part1)]
+fn part1(input: &[String]) -> String {{
+    todo!()
+}}
+
+#[aoc(day{day}, part2)]
+fn part2(input: &[String]) -> String {{
+    todo!()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn part1_example() {{
+{part1_example}
+    }}
+
+    #[test]
+    fn part2_example() {{
+{part2_example}
+    }}
+}}
+"
+    )
+}
+
+/// The day declared by a `mod dayN;` line
+fn declared_day(line: &str) -> Option<u32> {
+    line.trim()
+        .strip_prefix("mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Add `mod dayN;` to a year's `lib.rs`, whose days are declared latest first
+pub fn insert_mod(lib: &str, day: u32) -> Result<String, String> {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.iter().any(|l| declared_day(l) == Some(day)) {
+        return Err(format!("Day {day} is already declared in lib.rs"));
+    }
+    // Before the first earlier day, or after the last day when they are all later
+    let index = lines
+        .iter()
+        .position(|l| declared_day(l).is_some_and(|d| d < day))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|l| declared_day(l).is_some())
+                .map(|i| i + 1)
+        })
+        .unwrap_or(0);
+    let line = format!("mod day{day};");
+    lines.insert(index, &line);
+    if index == 0 && lines.get(1).is_some_and(|l| declared_day(l).is_none()) {
+        lines.insert(1, "");
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Create a new day in the year crate under `root`, returning the paths that were written
+pub fn scaffold(
+    root: &Path,
+    year: u32,
+    day: u32,
+    examples: &[Example],
+) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day {day}"));
+    }
+    let src = root.join(format!("aoc{year}/src"));
+    let lib_path = src.join("lib.rs");
+    let lib = fs::read_to_string(&lib_path).map_err(|e| {
+        format!(
+            "{}: {e}; the crate of the year has to be created first",
+            lib_path.display()
+        )
+    })?;
+    let day_path = src.join(format!("day{day}.rs"));
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()));
+    }
+
+    let lib = insert_mod(&lib, day)?;
+    let write = |path: &Path, contents: &str| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))
+    };
+
+    // The examples go first, as they are refused when the day has some already
+    let mut written = vec![];
+    if !examples.is_empty() {
+        written.extend(examples::save(root, year, day, examples)?);
+    }
+    write(&day_path, &template(day, examples))?;
+    written.push(day_path);
+    write(&lib_path, &lib)?;
+    written.push(lib_path);
+
+    let input = input_path(root, year, day);
+    if !input.exists() {
+        write(&input, "")?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_mod() {
+        let lib = "mod day10;\nmod day3;\nmod day1;\n\nuse aoc_runner_derive::aoc_lib;\n";
+        assert_eq!(
+            insert_mod(lib, 9).unwrap(),
+            "mod day10;\nmod day9;\nmod day3;\nmod day1;\n\nuse aoc_runner_derive::aoc_lib;\n"
+        );
+        assert!(insert_mod(lib, 11)
+            .unwrap()
+            .starts_with("mod day11;\nmod day10;\n"));
+        assert!(insert_mod(lib, 2)
+            .unwrap()
+            .contains("mod day3;\nmod day2;\nmod day1;\n"));
+        assert_eq!(
+            insert_mod(lib, 3),
+            Err(String::from("Day 3 is already declared in lib.rs"))
+        );
+        assert_eq!(
+            insert_mod("use aoc_runner_derive::aoc_lib;\n", 1).unwrap(),
+            "mod day1;\n\nuse aoc_runner_derive::aoc_lib;\n"
+        );
+    }
+
+    fn example(number: u32, answers: &[(u32, &str)]) -> Example {
+        Example {
+            number,
+            input: format!("example {number}\n"),
+            answers: answers.iter().map(|(p, a)| (*p, a.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_template() {
+        let placeholder = template(7, &[]);
+        assert!(placeholder.contains("#[aoc_generator(day7)]"));
+        assert_eq!(placeholder.matches("\"<EXAMPLE>\"").count(), 2);
+
+        let source = template(7, &[example(1, &[(1, "3")])]);
+        assert!(source.contains("part1(&parse(include_str!(\"../examples/day7/1.txt\")).unwrap())"));
+        assert!(source.contains("            \"3\"\n"));
+        assert_eq!(source.matches("\"<EXAMPLE>\"").count(), 1);
+
+        let source = template(7, &[example(1, &[(1, "3")]), example(2, &[(2, "4")])]);
+        assert!(source.contains("part2(&parse(include_str!(\"../examples/day7/2.txt\")).unwrap())"));
+        assert!(!source.contains("\"<EXAMPLE>\""));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc2099/src")).unwrap();
+        fs::write(root.join("aoc2099/src/lib.rs"), "mod day1;\n").unwrap();
+
+        let written = scaffold(&root, 2099, 2, &[example(1, &[(1, "3")])]).unwrap();
+        assert_eq!(written.len(), 5);
+        assert_eq!(
+            fs::read_to_string(root.join("aoc2099/src/lib.rs")).unwrap(),
+            "mod day2;\nmod day1;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("aoc2099/input/2099/day2.txt")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            examples::load(&root, 2099, 2).unwrap(),
+            vec![example(1, &[(1, "3")])]
+        );
+        assert!(scaffold(&root, 2099, 2, &[])
+            .unwrap_err()
+            .ends_with("day2.rs already exists"));
+        assert!(scaffold(&root, 2098, 1, &[]).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use aoc_runner::{ArcStr, Runner};
+
+/// Generates the input of a solution and wraps it in a runner, as registered by `cargo-aoc`
+pub type Factory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A solution registered with `#[aoc(dayN, partM)]` in one of the year crates
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The name of the variant, for days with several implementations of a part
+    pub name: Option<&'static str>,
+    pub factory: Factory,
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " ({name})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every solution compiled into the runner, ordered by year, day and part
+pub static SOLUTIONS: &[Solution] = include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// Every year compiled into the runner
+pub static YEARS: &[u32] = include!(concat!(env!("OUT_DIR"), "/years.rs"));
+
+/// Which solutions to run, each field narrowing the selection further
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+}
+
+impl Selection {
+    pub fn matches(&self, solution: &Solution) -> bool {
+        self.year.is_none_or(|y| y == solution.year)
+            && self.day.is_none_or(|d| d == solution.day)
+            && self.part.is_none_or(|p| p == solution.part)
+    }
+
+    /// The selected solutions, in order
+    pub fn solutions(&self) -> impl Iterator<Item = &'static Solution> + '_ {
+        SOLUTIONS.iter().filter(|s| self.matches(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_are_ordered() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day, w[0].part) <= (w[1].year, w[1].day, w[1].part)));
+    }
+
+    #[cfg(feature = "aoc2019")]
+    #[test]
+    fn test_selection() {
+        let selection = Selection {
+            year: Some(2019),
+            day: Some(4),
+            part: Some(1),
+        };
+        let names: Vec<_> = selection.solutions().map(|s| s.name).collect();
+        assert_eq!(names, vec![None, Some("brute_force")]);
+    }
+}
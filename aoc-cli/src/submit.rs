@@ -0,0 +1,346 @@
+//! Submit answers to adventofcode.com, remembering every attempt so that the site is only asked
+//! about answers it has not judged yet.
+//!
+//! The attempts are kept in `aoc-cli/submissions.tsv`, out of the repository, one per line as
+//! `YEAR DAY PART VERDICT ANSWER`. An answer is known without submitting it when it was tried
+//! before, when the part was solved already, or when it lies beyond an answer that was too high or
+//! too low.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::fetch::Client;
+
+/// What the site made of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The previous answer was too recent, so this one was not judged
+    Wait(Duration),
+    /// The part was solved already, or the previous part was not
+    WrongLevel,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::Wait(wait) => write!(f, "wait {}s", wait.as_secs()),
+            Self::WrongLevel => write!(f, "wrong level"),
+        }
+    }
+}
+
+/// The text of the `<article>` of a page, without tags and with its whitespace collapsed
+fn article_text(page: &str) -> Option<String> {
+    let start = page.find("<article")?;
+    let end = start + page[start..].find("</article>")?;
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Parse a wait such as `1m 23s`
+fn parse_wait(s: &str) -> Option<Duration> {
+    s.split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            let secs = match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(secs))
+        })
+}
+
+impl Verdict {
+    /// Read the verdict off the page the site answers a submission with
+    pub fn parse(page: &str) -> Result<Self, String> {
+        let text = article_text(page).ok_or("The response has no <article>")?;
+        if text.contains("That's the right answer") {
+            Ok(Self::Correct)
+        } else if text.contains("your answer is too high") {
+            Ok(Self::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Ok(Self::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Ok(Self::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            text.split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .map(Self::Wait)
+                .ok_or_else(|| format!("Could not read the time left to wait in [{text}]"))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Self::WrongLevel)
+        } else {
+            Err(format!("Unknown response [{text}]"))
+        }
+    }
+
+    fn to_field(self) -> String {
+        match self {
+            Self::Correct => String::from("correct"),
+            Self::TooHigh => String::from("too-high"),
+            Self::TooLow => String::from("too-low"),
+            Self::Wrong => String::from("wrong"),
+            Self::Wait(wait) => format!("wait-{}", wait.as_secs()),
+            Self::WrongLevel => String::from("wrong-level"),
+        }
+    }
+
+    fn from_field(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Self::Correct),
+            "too-high" => Some(Self::TooHigh),
+            "too-low" => Some(Self::TooLow),
+            "wrong" => Some(Self::Wrong),
+            "wrong-level" => Some(Self::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")?
+                .parse()
+                .ok()
+                .map(|secs| Self::Wait(Duration::from_secs(secs))),
+        }
+    }
+}
+
+/// An answer submitted for a part, and the site's verdict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        let mut next = || fields.next();
+        Some(Self {
+            year: next()?.parse().ok()?,
+            day: next()?.parse().ok()?,
+            part: next()?.parse().ok()?,
+            verdict: Verdict::from_field(next()?)?,
+            answer: next()?.to_string(),
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            self.verdict.to_field(),
+            self.answer
+        )
+    }
+}
+
+/// Every attempt so far, oldest first
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attempts {
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let attempts = s
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(n, l)| {
+                Attempt::parse(l).ok_or_else(|| format!("Invalid attempt line {}", n + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { attempts })
+    }
+
+    /// Load the attempts, of which there are none before the first submission
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::new();
+        for attempt in &self.attempts {
+            out.push_str(&attempt.to_line());
+            out.push('\n');
+        }
+        fs::write(path, out)
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// The verdict an answer is bound to get, judging by the previous attempts at the part
+    pub fn known(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<Verdict> {
+        let attempts: Vec<_> = self
+            .attempts
+            .iter()
+            .filter(|a| (a.year, a.day, a.part) == (year, day, part))
+            .collect();
+        let number = answer.parse::<i64>().ok();
+        let compare = |other: &str| number.zip(other.parse::<i64>().ok());
+
+        if let Some(correct) = attempts.iter().find(|a| a.verdict == Verdict::Correct) {
+            return Some(match compare(&correct.answer) {
+                _ if correct.answer == answer => Verdict::Correct,
+                Some((n, c)) if n > c => Verdict::TooHigh,
+                Some((n, c)) if n < c => Verdict::TooLow,
+                _ => Verdict::Wrong,
+            });
+        }
+        if let Some(attempt) = attempts.iter().find(|a| {
+            a.answer == answer && !matches!(a.verdict, Verdict::Wait(_) | Verdict::WrongLevel)
+        }) {
+            return Some(attempt.verdict);
+        }
+        attempts
+            .iter()
+            .find_map(|a| match (a.verdict, compare(&a.answer)) {
+                (Verdict::TooHigh, Some((n, high))) if n >= high => Some(Verdict::TooHigh),
+                (Verdict::TooLow, Some((n, low))) if n <= low => Some(Verdict::TooLow),
+                _ => None,
+            })
+    }
+}
+
+/// The path of the attempts file, which is kept out of the repository
+pub fn attempts_path(root: &Path) -> PathBuf {
+    root.join("aoc-cli/submissions.tsv")
+}
+
+/// A verdict, and whether it was known without asking the site
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    Known(Verdict),
+    Judged(Verdict),
+}
+
+impl Client {
+    /// Submit an answer to a part, unless its verdict is known from the previous attempts, and
+    /// record the site's verdict
+    pub fn submit(
+        &mut self,
+        attempts: &mut Attempts,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Submission, String> {
+        if let Some(verdict) = attempts.known(year, day, part, answer) {
+            return Ok(Submission::Known(verdict));
+        }
+        let level = part.to_string();
+        let page = self.request(
+            &format!("/{year}/day/{day}/answer"),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+        let verdict = Verdict::parse(&page)?;
+        attempts.record(Attempt {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+        Ok(Submission::Judged(verdict))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            year: 2015,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            Ok(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have <span>1m 5s</span> left to wait."
+            )),
+            Ok(Verdict::Wait(Duration::from_secs(65)))
+        );
+        assert!(Verdict::parse("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_attempts_round_trip() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt("12", Verdict::Wait(Duration::from_secs(30))));
+        attempts.record(attempt("a b", Verdict::Wrong));
+        let s: String = attempts
+            .attempts
+            .iter()
+            .map(|a| a.to_line() + "\n")
+            .collect();
+        assert_eq!(s, "2015\t1\t1\twait-30\t12\n2015\t1\t1\twrong\ta b\n");
+        assert_eq!(Attempts::parse(&s), Ok(attempts));
+    }
+
+    #[test]
+    fn test_known() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt("100", Verdict::TooHigh));
+        attempts.record(attempt("10", Verdict::TooLow));
+        attempts.record(attempt("50", Verdict::Wait(Duration::from_secs(5))));
+        attempts.record(attempt("40", Verdict::Wrong));
+
+        assert_eq!(attempts.known(2015, 1, 1, "150"), Some(Verdict::TooHigh));
+        assert_eq!(attempts.known(2015, 1, 1, "10"), Some(Verdict::TooLow));
+        assert_eq!(attempts.known(2015, 1, 1, "40"), Some(Verdict::Wrong));
+        assert_eq!(attempts.known(2015, 1, 1, "50"), None);
+        assert_eq!(attempts.known(2015, 1, 2, "150"), None);
+
+        attempts.record(attempt("42", Verdict::Correct));
+        assert_eq!(attempts.known(2015, 1, 1, "42"), Some(Verdict::Correct));
+        assert_eq!(attempts.known(2015, 1, 1, "43"), Some(Verdict::TooHigh));
+        assert_eq!(attempts.known(2015, 1, 1, "x"), Some(Verdict::Wrong));
+    }
+}
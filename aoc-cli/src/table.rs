@@ -0,0 +1,94 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A plain text table with columns padded to their widest cell
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<S: ToString>(header: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            header: header.into_iter().map(|h| h.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push<S: ToString>(&mut self, row: impl IntoIterator<Item = S>) {
+        self.rows
+            .push(row.into_iter().map(|c| c.to_string()).collect());
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<_> = self.header.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+
+    /// The table in Markdown, for pasting into the README or an issue
+    pub fn markdown(&self) -> String {
+        let row = |cells: &[String]| {
+            let cells: Vec<_> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+        let mut out = row(&self.header);
+        out.push_str(&row(&vec![String::from("---"); self.header.len()]));
+        for cells in &self.rows {
+            out.push_str(&row(cells));
+        }
+        out
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        let write_row = |f: &mut Formatter<'_>, row: &[String]| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, w)| format!("{cell:w$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())
+        };
+
+        write_row(f, &self.header)?;
+        let rule: Vec<_> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        write_row(f, &rule)?;
+        for row in &self.rows {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut table = Table::new(["Day", "Answer"]);
+        table.push(["1", "12345"]);
+        table.push(["10", "6"]);
+        assert_eq!(
+            table.to_string(),
+            "Day  Answer\n---  ------\n1    12345\n10   6\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let mut table = Table::new(["Day", "Answer"]);
+        table.push(["1", "a|b"]);
+        assert_eq!(
+            table.markdown(),
+            "| Day | Answer |\n| --- | --- |\n| 1 | a\\|b |\n"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Run every solution against its puzzle input and compare with the answers recorded in
+//! `aocYYYY/answers.txt`, and against the examples under `aocYYYY/examples` that the puzzle text
+//! gives an answer for. Parts without a recorded answer are skipped.
+
+mod common;
+
+use aoc_cli::examples;
+use aoc_cli::run::{run, run_from_disk, Outcome};
+
+use common::{assert_no_failures, Year};
+
+fn check_year(year: u32) {
+    let Year {
+        root,
+        answers,
+        selection,
+    } = Year::load(year);
+
+    let mut failures = vec![];
+    for (key, answer) in answers.iter() {
+        let registered = selection.solutions().any(|s| {
+            s.day == key.day
+                && s.part == key.part
+                && key.variant.as_deref().is_none_or(|v| s.name == Some(v))
+        });
+        if !registered {
+            failures.push(format!(
+                "{key:?}: answer {answer} is recorded for a missing solution"
+            ));
+        }
+    }
+    for solution in selection.solutions() {
+        for example in examples::load(&root, year, solution.day).unwrap() {
+            let Some(expected) = example.answers.get(&solution.part) else {
+                continue;
+            };
+            match run(solution, &example.input, None).outcome {
+                Outcome::Answer(answer) if &answer == expected => {}
+                outcome => failures.push(format!(
+                    "{solution} on example {}: expected {expected}, got {outcome}",
+                    example.number
+                )),
+            }
+        }
+
+        let Some(expected) = answers.get(solution) else {
+            continue;
+        };
+        match run_from_disk(&root, solution, None).outcome {
+            Outcome::Answer(answer) if answer == expected => {}
+            outcome => failures.push(format!("{solution}: expected {expected}, got {outcome}")),
+        }
+    }
+
+    assert_no_failures(&failures);
+}
+
+common::years! {
+    check_year:
+    "aoc2015" => answers_2015(2015),
+    "aoc2016" => answers_2016(2016),
+    "aoc2017" => answers_2017(2017),
+    "aoc2018" => answers_2018(2018),
+    "aoc2019" => answers_2019(2019),
+    "aoc2020" => answers_2020(2020),
+    "aoc2021" => answers_2021(2021),
+    "aoc2023" => answers_2023(2023),
+    "aoc2024" => answers_2024(2024),
+    "aoc2025" => answers_2025(2025),
+}
@@ -0,0 +1,49 @@
+//! The scaffolding of the tests that check every year compiled into the runner, one test per year.
+
+use std::path::PathBuf;
+
+use aoc_cli::answers::Answers;
+use aoc_cli::solutions::Selection;
+
+/// A year to check, with its recorded answers and its solutions
+pub struct Year {
+    pub root: PathBuf,
+    pub answers: Answers,
+    pub selection: Selection,
+}
+
+impl Year {
+    pub fn load(year: u32) -> Self {
+        let root = aoc_cli::root();
+        let answers = Answers::load(&root, year).unwrap();
+        let selection = Selection {
+            year: Some(year),
+            ..Selection::default()
+        };
+        Self {
+            root,
+            answers,
+            selection,
+        }
+    }
+}
+
+/// Fail with every failure found, one per line
+pub fn assert_no_failures(failures: &[String]) {
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// A test for each year compiled into the runner, calling `$check` with the year
+macro_rules! years {
+    ($check:ident: $($feature:literal => $name:ident($year:literal),)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            #[test]
+            fn $name() {
+                $check($year);
+            }
+        )*
+    };
+}
+
+pub(crate) use years;
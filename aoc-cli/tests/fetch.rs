@@ -0,0 +1,50 @@
+//! Fetch inputs from the stand-in server in `stub`
+
+mod stub;
+
+use std::fs;
+use std::time::Duration;
+
+use aoc_cli::fetch::{Client, Fetched};
+use aoc_cli::input_path;
+
+use stub::{fixture_path, Stub};
+
+#[test]
+fn test_fetch_input() {
+    let root = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+    let stub = Stub::fixtures();
+    let interval = Duration::from_millis(200);
+    let mut client = Client::new(&stub.url, "secret").min_interval(interval);
+
+    assert_eq!(client.fetch_input(&root, 2015, 1), Ok(Fetched::Downloaded));
+    assert_eq!(
+        fs::read_to_string(input_path(&root, 2015, 1)).unwrap(),
+        fs::read_to_string(fixture_path("/2015/day/1/input")).unwrap()
+    );
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2015/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    assert!(requests[0]
+        .header("User-Agent")
+        .is_some_and(|ua| ua.contains("github.com/avrahamappel/aoc")));
+
+    // A saved input is never downloaded again
+    assert_eq!(client.fetch_input(&root, 2015, 1), Ok(Fetched::Cached));
+    assert_eq!(stub.requests().len(), 1);
+
+    // The empty file left by `aoc new` does not count
+    fs::write(input_path(&root, 2015, 2), "").unwrap();
+    assert_eq!(client.fetch_input(&root, 2015, 2), Ok(Fetched::Downloaded));
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].at - requests[0].at >= interval);
+
+    let err = client.fetch_input(&root, 2015, 25).unwrap_err();
+    assert!(err.ends_with("not found, is it unlocked?"), "{err}");
+    assert!(!input_path(&root, 2015, 25).exists());
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,73 @@
+//! Submit answers to the stand-in server in `stub`, which knows a single answer of 74 to 2015 day
+//! 1 part 1 and asks to wait for part 2
+
+mod stub;
+
+use std::fs;
+use std::time::Duration;
+
+use aoc_cli::fetch::Client;
+use aoc_cli::submit::{Attempts, Submission, Verdict};
+
+use stub::{fixture_path, Stub};
+
+fn emulate_site() -> Stub {
+    Stub::start(|request| {
+        let form: Vec<_> = request
+            .body
+            .split('&')
+            .filter_map(|field| field.split_once('='))
+            .collect();
+        let page = match (form.as_slice(), request.path.as_str()) {
+            ([("level", "1"), ("answer", answer)], "/2015/day/1/answer") => {
+                match answer.parse::<u32>().map(|a| a.cmp(&74)) {
+                    Ok(std::cmp::Ordering::Equal) => "correct",
+                    Ok(std::cmp::Ordering::Greater) => "too-high",
+                    _ => "too-low",
+                }
+            }
+            ([("level", "2"), _], "/2015/day/1/answer") => "wait",
+            _ => "wrong-level",
+        };
+        let path = fixture_path(&format!("answer/{page}.html"));
+        (200, fs::read_to_string(path).unwrap())
+    })
+}
+
+#[test]
+fn test_submit() {
+    let stub = emulate_site();
+    let mut client = Client::new(&stub.url, "secret").min_interval(Duration::ZERO);
+    let mut attempts = Attempts::default();
+    let mut submit = |part, answer| client.submit(&mut attempts, 2015, 1, part, answer);
+
+    assert_eq!(submit(1, "100"), Ok(Submission::Judged(Verdict::TooHigh)));
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2015/day/1/answer");
+    assert_eq!(requests[0].body, "level=1&answer=100");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+    // A known wrong answer is never submitted again, nor one beyond a known bound
+    assert_eq!(submit(1, "100"), Ok(Submission::Known(Verdict::TooHigh)));
+    assert_eq!(submit(1, "120"), Ok(Submission::Known(Verdict::TooHigh)));
+    assert_eq!(submit(1, "5"), Ok(Submission::Judged(Verdict::TooLow)));
+    assert_eq!(submit(1, "3"), Ok(Submission::Known(Verdict::TooLow)));
+    assert_eq!(stub.requests().len(), 2);
+
+    assert_eq!(submit(1, "74"), Ok(Submission::Judged(Verdict::Correct)));
+    assert_eq!(submit(1, "74"), Ok(Submission::Known(Verdict::Correct)));
+    assert_eq!(submit(1, "80"), Ok(Submission::Known(Verdict::TooHigh)));
+    assert_eq!(stub.requests().len(), 3);
+
+    // An answer that was not judged can be submitted again
+    let wait = Verdict::Wait(Duration::from_secs(34));
+    assert_eq!(submit(2, "1795"), Ok(Submission::Judged(wait)));
+    assert_eq!(submit(2, "1795"), Ok(Submission::Judged(wait)));
+    assert_eq!(stub.requests().len(), 5);
+
+    let path = std::env::temp_dir().join(format!("aoc-submit-{}.tsv", std::process::id()));
+    attempts.save(&path).unwrap();
+    assert_eq!(Attempts::load(&path), Ok(attempts));
+    fs::remove_file(&path).unwrap();
+}
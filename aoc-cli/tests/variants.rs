@@ -0,0 +1,43 @@
+//! Run every variant of the parts that have several on the saved examples and the puzzle input,
+//! and check that they agree, except for the variants recorded as known to disagree in
+//! `aocYYYY/answers.txt`.
+
+mod common;
+
+use aoc_cli::check::{cross_check, inputs, variants};
+
+use common::{assert_no_failures, Year};
+
+fn check_year(year: u32) {
+    let Year {
+        root,
+        answers,
+        selection,
+    } = Year::load(year);
+
+    let mut failures = vec![];
+    for part in variants(selection) {
+        let inputs = inputs(&root, part[0]).unwrap();
+        for disagreement in cross_check(&part, &inputs, &answers, None) {
+            if !disagreement.known {
+                failures.push(disagreement.to_string());
+            }
+        }
+    }
+
+    assert_no_failures(&failures);
+}
+
+common::years! {
+    check_year:
+    "aoc2015" => variants_2015(2015),
+    "aoc2016" => variants_2016(2016),
+    "aoc2017" => variants_2017(2017),
+    "aoc2018" => variants_2018(2018),
+    "aoc2019" => variants_2019(2019),
+    "aoc2020" => variants_2020(2020),
+    "aoc2021" => variants_2021(2021),
+    "aoc2023" => variants_2023(2023),
+    "aoc2024" => variants_2024(2024),
+    "aoc2025" => variants_2025(2025),
+}
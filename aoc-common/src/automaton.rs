@@ -0,0 +1,286 @@
+//! Cellular automata of live and dead cells on a [`Grid`], like Conway's Game of Life.
+//!
+//! An automaton is a rule in the usual B/S notation, such as `B3/S23` for Life, along with the
+//! cells that count as neighbors, what lies beyond the edges of the grid, and the cells that are
+//! stuck in a state whatever their neighbors do.
+
+use std::fmt::{self, Display, Formatter};
+use std::mem;
+use std::str::FromStr;
+
+use crate::parse::{ParseError, Source};
+use crate::Grid;
+
+/// The neighbor counts for which a dead cell is born and a live cell survives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`
+    pub const LIFE: Self = Self::new(&[3], &[2, 3]);
+
+    pub const fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let mut rule = Self {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+        let mut i = 0;
+        while i < birth.len() {
+            rule.birth[birth[i]] = true;
+            i += 1;
+        }
+        let mut i = 0;
+        while i < survival.len() {
+            rule.survival[survival[i]] = true;
+            i += 1;
+        }
+        rule
+    }
+
+    /// Whether a cell is alive in the next generation
+    pub fn next(&self, alive: bool, neighbors: usize) -> bool {
+        if alive {
+            self.survival[neighbors]
+        } else {
+            self.birth[neighbors]
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    /// Parse a rule in B/S notation, like `B3/S23` or `B36/S23`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (birth, survival) = source.split_once(s.trim(), "/")?;
+        let counts = |s: &str, prefix: char| {
+            let digits = s
+                .strip_prefix([prefix, prefix.to_ascii_lowercase()])
+                .ok_or_else(|| source.error(s, format!("{prefix} and neighbor counts")))?;
+            let mut counts = [false; 9];
+            for (i, c) in digits.char_indices() {
+                match c.to_digit(10) {
+                    Some(n @ 0..=8) => counts[n as usize] = true,
+                    _ => {
+                        let found = &digits[i..i + c.len_utf8()];
+                        return Err(source.error(found, "a neighbor count up to 8"));
+                    }
+                }
+            }
+            Ok(counts)
+        };
+        Ok(Self {
+            birth: counts(birth, 'B')?,
+            survival: counts(survival, 'S')?,
+        })
+    }
+}
+
+/// The cells that count as neighbors of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The eight cells sharing an edge or a corner
+    Moore,
+    /// The four cells sharing an edge
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Moore => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Self::VonNeumann => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+        }
+    }
+}
+
+/// What lies beyond the edges of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Dead cells
+    Dead,
+    /// The opposite edge, as if the grid was wrapped around a torus
+    Toroidal,
+}
+
+/// A grid of cells evolving under a rule, one generation per step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Automaton {
+    rule: Rule,
+    neighborhood: Neighborhood,
+    boundary: Boundary,
+    /// The cells stuck in a state, with their state
+    fixed: Vec<(usize, bool)>,
+    cells: Grid<bool>,
+    /// The next generation, kept around to save allocating a grid on every step
+    next: Grid<bool>,
+}
+
+impl Automaton {
+    /// An automaton with the Moore neighborhood and dead cells beyond the edges
+    pub fn new(cells: Grid<bool>, rule: Rule) -> Self {
+        Self {
+            rule,
+            neighborhood: Neighborhood::Moore,
+            boundary: Boundary::Dead,
+            fixed: vec![],
+            next: cells.clone(),
+            cells,
+        }
+    }
+
+    pub fn neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Keep a cell in a state whatever its neighbors do, starting now
+    pub fn fix(mut self, index: usize, alive: bool) -> Self {
+        self.cells[index] = alive;
+        self.fixed.push((index, alive));
+        self
+    }
+
+    pub fn cells(&self) -> &Grid<bool> {
+        &self.cells
+    }
+
+    /// The number of live neighbors of a cell
+    pub fn live_neighbors(&self, index: usize) -> usize {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let (x, y) = self.cells.coords(index);
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter(|(dx, dy)| {
+                let neighbor = match self.boundary {
+                    Boundary::Dead => x
+                        .checked_add_signed(*dx)
+                        .zip(y.checked_add_signed(*dy))
+                        .and_then(|(x, y)| self.cells.get(x, y)),
+                    Boundary::Toroidal => self.cells.get(
+                        (x + width).wrapping_add_signed(*dx) % width,
+                        (y + height).wrapping_add_signed(*dy) % height,
+                    ),
+                };
+                neighbor.is_some_and(|alive| *alive)
+            })
+            .count()
+    }
+
+    /// Move on to the next generation
+    pub fn step(&mut self) {
+        for i in 0..self.cells.len() {
+            self.next[i] = self.rule.next(self.cells[i], self.live_neighbors(i));
+        }
+        for (i, alive) in &self.fixed {
+            self.next[*i] = *alive;
+        }
+        mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    pub fn steps(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    pub fn alive(&self) -> usize {
+        self.cells.iter().filter(|alive| **alive).count()
+    }
+}
+
+impl Display for Automaton {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.cells.rows() {
+            for alive in row {
+                write!(f, "{}", if *alive { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn automaton(input: &str, rule: Rule) -> Automaton {
+        Automaton::new(Grid::parse(input, |c| c == '#'), rule)
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!("B3/S23".parse(), Ok(Rule::LIFE));
+        assert_eq!("b36/s23".parse(), Ok(Rule::new(&[3, 6], &[2, 3])));
+        assert_eq!("B/S".parse(), Ok(Rule::new(&[], &[])));
+        let err = "B39/S23".parse::<Rule>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a neighbor count up to 8, found [9]"
+        );
+        let err = "B3é/S23".parse::<Rule>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a neighbor count up to 8, found [é]"
+        );
+        assert!("S23/B3".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut life = automaton(".....\n..#..\n..#..\n..#..\n.....", Rule::LIFE);
+        life.step();
+        assert_eq!(life.to_string(), ".....\n.....\n.###.\n.....\n.....\n");
+        life.step();
+        assert_eq!(life.to_string(), ".....\n..#..\n..#..\n..#..\n.....\n");
+    }
+
+    #[test]
+    fn test_boundary() {
+        // A glider comes back to where it started after crossing a torus of 6x6 cells in 24 steps
+        let glider = ".#....\n..#...\n###...\n......\n......\n......";
+        let mut life = automaton(glider, Rule::LIFE).boundary(Boundary::Toroidal);
+        let start = life.cells().clone();
+        life.steps(24);
+        assert_eq!(life.cells(), &start);
+
+        // It ends up as a block against dead edges instead
+        let mut life = automaton(glider, Rule::LIFE);
+        life.steps(24);
+        assert_eq!(
+            life.to_string(),
+            "......\n......\n......\n......\n....##\n....##\n"
+        );
+    }
+
+    #[test]
+    fn test_neighborhood_and_fixed() {
+        let mut cross = automaton("...\n.#.\n...", Rule::new(&[1], &[]))
+            .neighborhood(Neighborhood::VonNeumann)
+            .fix(0, true);
+        assert_eq!(cross.live_neighbors(1), 2);
+        assert_eq!(cross.live_neighbors(8), 0);
+        cross.step();
+        assert_eq!(cross.to_string(), "#..\n..#\n.#.\n");
+    }
+}
@@ -0,0 +1,93 @@
+//! Cooperative cancellation of solutions that run for too long.
+//!
+//! The runner gives up on a solution after its time limit, but cannot stop the thread running it.
+//! It cancels the token installed on that thread instead, and long loops call [`checkpoint`] to
+//! unwind as soon as that happens, so that an abandoned solution does not keep a core busy while
+//! the next ones are timed. Outside of the runner, as in tests, no token is installed and the
+//! checks do nothing.
+
+use std::cell::RefCell;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared between the runner and the thread running a solution
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The payload a cancelled solution unwinds with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `token` installed on the current thread
+pub fn with_token<T>(token: Token, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(token)));
+    // Restore the previous token even when `f` unwinds
+    struct Restore(Option<Token>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Whether the token of the current thread was cancelled, for solutions that would rather stop
+/// on their own
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().is_some_and(Token::is_cancelled))
+}
+
+/// Unwind with [`Cancelled`] when the token of the current thread was cancelled. This does not go
+/// through the panic hook, so nothing is printed.
+pub fn checkpoint() {
+    if is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint() {
+        // Nothing happens without a token
+        checkpoint();
+        assert!(!is_cancelled());
+
+        let token = Token::new();
+        let loops = panic::catch_unwind(|| {
+            with_token(token.clone(), || {
+                for i in 0.. {
+                    if i == 10 {
+                        token.cancel();
+                    }
+                    checkpoint();
+                }
+            })
+        });
+        let payload = loops.unwrap_err();
+        assert_eq!(payload.downcast_ref::<Cancelled>(), Some(&Cancelled));
+        assert!(!is_cancelled());
+    }
+}
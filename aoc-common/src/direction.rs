@@ -0,0 +1,108 @@
+/// One of the four directions on a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Parse a direction from its initial (`U`, `D`, `L` or `R`)
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Self::Up),
+            'D' => Some(Self::Down),
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    /// The initial of the direction, the inverse of [`Direction::from_char`]
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Up => 'U',
+            Self::Down => 'D',
+            Self::Left => 'L',
+            Self::Right => 'R',
+        }
+    }
+
+    /// The offset of a single step in this direction, with y growing upwards
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Self::Up => (0, 1),
+            Self::Down => (0, -1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    /// The direction faced after turning
+    pub fn turn(self, turn: Turn) -> Self {
+        match (self, turn) {
+            (Self::Up, Turn::Left) | (Self::Down, Turn::Right) => Self::Left,
+            (Self::Up, Turn::Right) | (Self::Down, Turn::Left) => Self::Right,
+            (Self::Left, Turn::Left) | (Self::Right, Turn::Right) => Self::Down,
+            (Self::Left, Turn::Right) | (Self::Right, Turn::Left) => Self::Up,
+        }
+    }
+}
+
+/// A turn or rotation to the left or right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// Parse a turn from its initial (`L` or `R`)
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_char() {
+        for dir in Direction::ALL {
+            assert_eq!(Direction::from_char(dir.to_char()), Some(dir));
+        }
+        assert_eq!(Direction::from_char('X'), None);
+        assert_eq!(Turn::from_char('L'), Some(Turn::Left));
+    }
+
+    #[test]
+    fn test_turn() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn(Turn::Left).turn(Turn::Right), dir);
+            assert_eq!(dir.turn(Turn::Left).turn(Turn::Left), dir.opposite());
+        }
+        assert_eq!(Direction::Up.turn(Turn::Right), Direction::Right);
+    }
+}
@@ -0,0 +1,253 @@
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{ParseError, Source};
+
+/// Offsets of the cells sharing an edge with a cell
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the cells sharing an edge or a corner with a cell
+const ALL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row, addressed by index or by `(x, y)` coordinates with
+/// `(0, 0)` at the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    line_len: usize,
+    vec: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(line_len: usize, vec: Vec<T>) -> Self {
+        assert!(line_len > 0 && vec.len().is_multiple_of(line_len));
+        Self { line_len, vec }
+    }
+
+    /// Parse a grid with one line per row, converting each character into a cell.
+    /// Lines are trimmed, so indented examples parse the same as puzzle inputs.
+    pub fn parse(input: &str, f: impl FnMut(char) -> T) -> Self {
+        let lines: Vec<_> = input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        let line_len = lines.first().map_or(1, |l| l.chars().count());
+        let vec = lines.iter().flat_map(|l| l.chars()).map(f).collect();
+        Self::new(line_len, vec)
+    }
+
+    /// Parse a grid like [`Grid::parse`], failing on the first character `f` rejects, on a row
+    /// of a different length than the first one, or on an input without rows, so that the grid
+    /// always has corners
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let lines: Vec<_> = source.lines().collect();
+        let Some(first) = lines.first() else {
+            return Err(source.end_error(input, "a row of cells"));
+        };
+        let line_len = first.chars().count();
+        let mut vec = Vec::with_capacity(line_len * lines.len());
+        for line in lines {
+            if line.chars().count() != line_len {
+                return Err(source.error(line, format!("a row of {line_len} cells")));
+            }
+            for (i, c) in line.char_indices() {
+                let cell =
+                    f(c).ok_or_else(|| source.error(&line[i..i + c.len_utf8()], expected))?;
+                vec.push(cell);
+            }
+        }
+        Ok(Self::new(line_len, vec))
+    }
+
+    pub fn width(&self) -> usize {
+        self.line_len
+    }
+
+    pub fn height(&self) -> usize {
+        self.vec.len() / self.line_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Get the index of the top left corner of the grid
+    #[allow(clippy::unused_self)]
+    pub fn top_left_corner(&self) -> usize {
+        0
+    }
+
+    /// Get the index of the top right corner of the grid
+    pub fn top_right_corner(&self) -> usize {
+        self.line_len - 1
+    }
+
+    /// Get the index of the bottom left corner of the grid
+    pub fn bottom_left_corner(&self) -> usize {
+        (self.height() - 1) * self.line_len
+    }
+
+    /// Get the index of the bottom right corner of the grid
+    pub fn bottom_right_corner(&self) -> usize {
+        self.vec.len() - 1
+    }
+
+    /// The indices of the four corners of the grid
+    pub fn corners(&self) -> [usize; 4] {
+        [
+            self.top_left_corner(),
+            self.top_right_corner(),
+            self.bottom_left_corner(),
+            self.bottom_right_corner(),
+        ]
+    }
+
+    /// Convert an index into `(x, y)` coordinates
+    pub fn coords(&self, index: usize) -> (usize, usize) {
+        (index % self.line_len, index / self.line_len)
+    }
+
+    /// Convert `(x, y)` coordinates into an index, if they are within the grid
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width() && y < self.height()).then_some(y * self.line_len + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.vec[i])
+    }
+
+    fn offsets(
+        &self,
+        index: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = usize> + '_ {
+        assert!(index < self.vec.len());
+        let (x, y) = self.coords(index);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            self.index_of(x.checked_add_signed(*dx)?, y.checked_add_signed(*dy)?)
+        })
+    }
+
+    /// Indices of the cells sharing an edge with the given cell
+    pub fn orthogonal_neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.offsets(index, &ORTHOGONAL)
+    }
+
+    /// Indices of the cells sharing an edge or a corner with the given cell
+    pub fn all_neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.offsets(index, &ALL)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.vec.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.vec.iter_mut()
+    }
+
+    /// Iterate over the rows of the grid
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.vec.chunks(self.line_len)
+    }
+
+    /// Build a grid of the same shape by converting every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            line_len: self.line_len,
+            vec: self.vec.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.vec[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.vec[index]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(
+            "123
+             456",
+            |c| c.to_digit(10).unwrap(),
+        );
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 1), None);
+        assert_eq!(grid.corners(), [0, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            Grid::try_parse("12\n34", digit, "a digit"),
+            Ok(Grid::new(2, vec![1, 2, 3, 4]))
+        );
+        let err = Grid::try_parse("12\n3x", digit, "a digit").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected a digit, found [x]"
+        );
+        let err = Grid::try_parse("12\n345", digit, "a digit").unwrap_err();
+        assert_eq!(err.expected, "a row of 2 cells");
+        let err = Grid::try_parse("\n  \n", digit, "a digit").unwrap_err();
+        assert_eq!(err.expected, "a row of cells");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse(
+            "123
+             456
+             789",
+            |c| c.to_digit(10).unwrap(),
+        );
+        let values = |it: &mut dyn Iterator<Item = usize>| it.map(|i| grid[i]).collect::<Vec<_>>();
+        assert_eq!(values(&mut grid.orthogonal_neighbors(0)), vec![2, 4]);
+        assert_eq!(values(&mut grid.orthogonal_neighbors(4)), vec![2, 4, 6, 8]);
+        assert_eq!(values(&mut grid.all_neighbors(8)), vec![5, 6, 8]);
+        assert_eq!(grid.all_neighbors(4).count(), 8);
+    }
+}
@@ -0,0 +1,20 @@
+//! Building blocks shared by the solutions of every year
+
+pub mod automaton;
+pub mod cancel;
+pub mod direction;
+pub mod grid;
+pub mod logging;
+pub mod ocr;
+pub mod packed;
+pub mod parse;
+pub mod point;
+pub mod render;
+pub mod search;
+
+pub use automaton::{Automaton, Boundary, Neighborhood, Rule};
+pub use direction::{Direction, Turn};
+pub use grid::Grid;
+pub use packed::PackedAutomaton;
+pub use parse::ParseError;
+pub use point::Point;
@@ -0,0 +1,22 @@
+//! Diagnostics for the solutions, which log with the `log` macros and stay silent unless asked.
+//!
+//! Messages are logged under the module they come from, such as `aoc2016::day10`, so the `AOC_LOG`
+//! environment variable can pick years and days along with a level:
+//! `AOC_LOG=debug`, `AOC_LOG=aoc2016=debug` or `AOC_LOG=aoc2016::day10=trace,aoc2024=info`.
+
+use env_logger::{Builder, Env};
+use log::LevelFilter;
+
+/// The environment variable holding the log filter
+pub const ENV_VAR: &str = "AOC_LOG";
+
+/// Install the logger configured by `AOC_LOG`, writing to stderr. Nothing is logged when the
+/// variable is not set, and installing the logger a second time does nothing, so the generators
+/// of the days that log call it too, for their diagnostics to show under `cargo aoc`.
+pub fn init() {
+    let _ = Builder::new()
+        .filter_level(LevelFilter::Off)
+        .parse_env(Env::new().filter(ENV_VAR))
+        .format_timestamp(None)
+        .try_init();
+}
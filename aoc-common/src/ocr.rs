@@ -0,0 +1,100 @@
+//! Reading the capital letters that some puzzles draw on a screen, 6 pixels high and 5 columns
+//! apart, so that their answers can be returned rather than read off a picture.
+
+/// The height of a letter in pixels
+pub const HEIGHT: usize = 6;
+
+/// The columns taken by each letter, including the gap before the next one
+pub const WIDTH: usize = 5;
+
+/// The letters known so far, with `#` for the pixels that are lit
+const LETTERS: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##..", "#..#.", "#..#.", "####.", "#..#.", "#..#."]),
+    ('B', ["###..", "#..#.", "###..", "#..#.", "#..#.", "###.."]),
+    ('C', [".##..", "#..#.", "#....", "#....", "#..#.", ".##.."]),
+    ('E', ["####.", "#....", "###..", "#....", "#....", "####."]),
+    ('F', ["####.", "#....", "###..", "#....", "#....", "#...."]),
+    ('G', [".##..", "#..#.", "#....", "#.##.", "#..#.", ".###."]),
+    ('H', ["#..#.", "#..#.", "####.", "#..#.", "#..#.", "#..#."]),
+    ('I', [".###.", "..#..", "..#..", "..#..", "..#..", ".###."]),
+    ('J', ["..##.", "...#.", "...#.", "...#.", "#..#.", ".##.."]),
+    ('K', ["#..#.", "#.#..", "##...", "#.#..", "#.#..", "#..#."]),
+    ('L', ["#....", "#....", "#....", "#....", "#....", "####."]),
+    ('O', [".##..", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('P', ["###..", "#..#.", "#..#.", "###..", "#....", "#...."]),
+    ('R', ["###..", "#..#.", "#..#.", "###..", "#.#..", "#..#."]),
+    ('S', [".###.", "#....", "#....", ".##..", "...#.", "###.."]),
+    ('U', ["#..#.", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####.", "...#.", "..#..", ".#...", "#....", "####."]),
+];
+
+/// Read the letters of a screen of `width` by [`HEIGHT`] pixels, where `lit` tells whether the
+/// pixel at `(x, y)` is on, or `None` if some letter is not one of the known ones
+pub fn read(width: usize, lit: impl Fn(usize, usize) -> bool) -> Option<String> {
+    (0..width.div_ceil(WIDTH))
+        .map(|i| {
+            let pixel = |x: usize, y: usize| {
+                let x = i * WIDTH + x;
+                x < width && lit(x, y)
+            };
+            LETTERS
+                .iter()
+                .find(|(_, rows)| {
+                    rows.iter().enumerate().all(|(y, row)| {
+                        row.bytes()
+                            .enumerate()
+                            .all(|(x, b)| (b == b'#') == pixel(x, y))
+                    })
+                })
+                .map(|(c, _)| *c)
+        })
+        .collect()
+}
+
+/// Draw a screen of `width` by [`HEIGHT`] pixels, for when [`read`] does not know its letters
+pub fn draw(width: usize, lit: impl Fn(usize, usize) -> bool) -> String {
+    (0..HEIGHT)
+        .map(|y| {
+            (0..width)
+                .map(|x| if lit(x, y) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let screen = [
+            ".##..###..#...##..#.",
+            "#..#.#..#.#...#.#.#.",
+            "#..#.###..#....#..#.",
+            "####.#..#.#....#..#.",
+            "#..#.#..#.#....#..#.",
+            "#..#.###..####.#..#.",
+        ];
+        let lit = |x: usize, y: usize| screen[y].as_bytes()[x] == b'#';
+        // The pixels after the first two letters do not make up known letters
+        assert_eq!(read(20, lit), None);
+        assert_eq!(read(10, lit), Some(String::from("AB")));
+        assert_eq!(draw(10, lit).lines().next(), Some(".##..###.."));
+
+        let screen = [
+            "####.#...#",
+            "...#.#...#",
+            "..#...#.#.",
+            ".#.....#..",
+            "#......#..",
+            "####...#..",
+        ];
+        assert_eq!(
+            read(10, |x, y| screen[y].as_bytes()[x] == b'#'),
+            Some(String::from("ZY"))
+        );
+    }
+}
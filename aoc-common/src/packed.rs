@@ -0,0 +1,292 @@
+//! A bit-packed [`Automaton`](crate::Automaton) for the Moore neighborhood, for large grids and
+//! many generations.
+//!
+//! Each row is stored as 64 cells per word, and the neighbors of the 64 cells of a word are counted
+//! at once with bitwise adders over the eight shifted neighbor words. The next generation is written
+//! into a second buffer that is swapped in, so stepping allocates nothing.
+
+use std::mem;
+
+use crate::automaton::{Boundary, Rule};
+use crate::Grid;
+
+const BITS: usize = u64::BITS as usize;
+
+/// The sum and the carry of three bits, 64 at a time
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let ab = a ^ b;
+    (ab ^ c, (a & b) | (ab & c))
+}
+
+fn half_add(a: u64, b: u64) -> (u64, u64) {
+    (a ^ b, a & b)
+}
+
+/// The cells whose count, as bits of weight 1, 2, 4 and 8, is `n`
+fn count_is(n: usize, count: [u64; 4]) -> u64 {
+    count.iter().enumerate().fold(!0, |mask, (bit, c)| {
+        mask & if n >> bit & 1 == 1 { *c } else { !*c }
+    })
+}
+
+/// Set the cell at `index` of a grid of `width` cells stored in rows of `stride` words
+fn set(words: &mut [u64], stride: usize, width: usize, index: usize, alive: bool) {
+    let (x, y) = (index % width, index / width);
+    let word = &mut words[y * stride + x / BITS];
+    let bit = 1 << (x % BITS);
+    if alive {
+        *word |= bit;
+    } else {
+        *word &= !bit;
+    }
+}
+
+/// A grid of cells evolving under a rule in the Moore neighborhood, stored as bits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedAutomaton {
+    rule: Rule,
+    boundary: Boundary,
+    /// The cells stuck in a state, with their state
+    fixed: Vec<(usize, bool)>,
+    width: usize,
+    height: usize,
+    /// The words of a row
+    stride: usize,
+    cells: Vec<u64>,
+    next: Vec<u64>,
+}
+
+impl PackedAutomaton {
+    /// An automaton with dead cells beyond the edges
+    pub fn new(cells: &Grid<bool>, rule: Rule) -> Self {
+        let (width, height) = (cells.width(), cells.height());
+        let stride = width.div_ceil(BITS);
+        let mut words = vec![0; stride * height];
+        for (i, alive) in cells.iter().enumerate() {
+            let (x, y) = cells.coords(i);
+            words[y * stride + x / BITS] |= u64::from(*alive) << (x % BITS);
+        }
+        Self {
+            rule,
+            boundary: Boundary::Dead,
+            fixed: vec![],
+            width,
+            height,
+            stride,
+            next: words.clone(),
+            cells: words,
+        }
+    }
+
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Keep a cell in a state whatever its neighbors do, starting now
+    pub fn fix(mut self, index: usize, alive: bool) -> Self {
+        self.fixed.push((index, alive));
+        set(&mut self.cells, self.stride, self.width, index, alive);
+        self
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        (x < self.width && y < self.height)
+            .then(|| self.cells[y * self.stride + x / BITS] >> (x % BITS) & 1 == 1)
+    }
+
+    /// The cells as a grid
+    pub fn to_grid(&self) -> Grid<bool> {
+        let cells = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| self.get(x, y).unwrap_or_default())
+            .collect();
+        Grid::new(self.width, cells)
+    }
+
+    pub fn alive(&self) -> usize {
+        self.cells.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The words of the row `dy` away from `y`, or `None` beyond a dead edge
+    fn row(&self, y: usize, dy: isize) -> Option<&[u64]> {
+        let y = match self.boundary {
+            Boundary::Dead => y.checked_add_signed(dy).filter(|y| *y < self.height)?,
+            Boundary::Toroidal => (y + self.height).wrapping_add_signed(dy) % self.height,
+        };
+        Some(&self.cells[y * self.stride..(y + 1) * self.stride])
+    }
+
+    /// A row shifted so that each cell lines up with its west and east neighbors
+    fn shifted(&self, row: &[u64], w: usize) -> (u64, u64) {
+        let last = self.stride - 1;
+        let toroidal = self.boundary == Boundary::Toroidal;
+        let cell = |x: usize| row[x / BITS] >> (x % BITS) & 1;
+
+        let west_in = if w > 0 {
+            row[w - 1] >> (BITS - 1)
+        } else if toroidal {
+            cell(self.width - 1)
+        } else {
+            0
+        };
+        let west = row[w] << 1 | west_in;
+
+        let mut east = row[w] >> 1;
+        if w < last {
+            east |= row[w + 1] << (BITS - 1);
+        } else if toroidal {
+            east |= cell(0) << ((self.width - 1) % BITS);
+        }
+        (west, east)
+    }
+
+    /// Move on to the next generation
+    pub fn step(&mut self) {
+        // The cells of the last word of a row that are within the width
+        let padding = match self.width % BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        };
+        let mut next = mem::take(&mut self.next);
+
+        for y in 0..self.height {
+            let (above, row, below) = (self.row(y, -1), self.row(y, 0).unwrap(), self.row(y, 1));
+            for w in 0..self.stride {
+                let (west, east) = self.shifted(row, w);
+                let (above_west, above_east) = above.map_or((0, 0), |r| self.shifted(r, w));
+                let (below_west, below_east) = below.map_or((0, 0), |r| self.shifted(r, w));
+                let (above, below) = (above.map_or(0, |r| r[w]), below.map_or(0, |r| r[w]));
+
+                let (sum_a, carry_a) = full_add(above_west, above, above_east);
+                let (sum_b, carry_b) = full_add(west, east, below_west);
+                let (sum_c, carry_c) = half_add(below, below_east);
+                let (ones, carry_d) = full_add(sum_a, sum_b, sum_c);
+                let (twos_sum, fours_a) = full_add(carry_a, carry_b, carry_c);
+                let (twos, fours_b) = half_add(twos_sum, carry_d);
+                let (fours, eights) = half_add(fours_a, fours_b);
+                let count = [ones, twos, fours, eights];
+
+                let alive = row[w];
+                let mask = |alive: bool| {
+                    (0..=8)
+                        .filter(|n| self.rule.next(alive, *n))
+                        .fold(0, |mask, n| mask | count_is(n, count))
+                };
+                let mut cells = (alive & mask(true)) | (!alive & mask(false));
+                if w == self.stride - 1 {
+                    cells &= padding;
+                }
+                next[y * self.stride + w] = cells;
+            }
+        }
+        for (i, alive) in &self.fixed {
+            set(&mut next, self.stride, self.width, *i, *alive);
+        }
+        self.next = mem::replace(&mut self.cells, next);
+    }
+
+    pub fn steps(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::Automaton;
+
+    /// A reproducible soup of cells, about a third of them alive
+    fn soup(width: usize, height: usize, seed: u64) -> Grid<bool> {
+        let mut state = seed;
+        let cells = (0..width * height)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state.is_multiple_of(3)
+            })
+            .collect();
+        Grid::new(width, cells)
+    }
+
+    #[test]
+    fn test_count_is() {
+        let count = [0b1010, 0b0110, 0, 0];
+        assert_eq!(count_is(0, count) & 0b1111, 0b0001);
+        assert_eq!(count_is(1, count) & 0b1111, 0b1000);
+        assert_eq!(count_is(2, count) & 0b1111, 0b0100);
+        assert_eq!(count_is(3, count) & 0b1111, 0b0010);
+    }
+
+    #[test]
+    fn test_same_as_automaton() {
+        let rules = [
+            Rule::LIFE,
+            "B36/S23".parse().unwrap(),
+            "B2/S".parse().unwrap(),
+        ];
+        // Widths on both sides of a word, and one spanning several
+        for (width, height) in [(10, 7), (64, 5), (65, 9), (130, 12)] {
+            let cells = soup(width, height, (width * height) as u64);
+            for rule in rules {
+                for boundary in [Boundary::Dead, Boundary::Toroidal] {
+                    let mut slow = Automaton::new(cells.clone(), rule)
+                        .boundary(boundary)
+                        .fix(width - 1, true)
+                        .fix(width + 1, false);
+                    let mut packed = PackedAutomaton::new(&cells, rule)
+                        .boundary(boundary)
+                        .fix(width - 1, true)
+                        .fix(width + 1, false);
+                    for generation in 0..20 {
+                        assert_eq!(
+                            packed.to_grid(),
+                            *slow.cells(),
+                            "{width}x{height} {rule:?} {boundary:?} generation {generation}"
+                        );
+                        assert_eq!(packed.alive(), slow.alive());
+                        slow.step();
+                        packed.step();
+                    }
+                }
+            }
+        }
+    }
+
+    /// A benchmark rather than a test, run with `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_large_grid() {
+        /// Cells updated per second over `generations` steps
+        fn rate(generations: usize, mut step: impl FnMut(usize)) -> f64 {
+            let start = Instant::now();
+            step(generations);
+            (4096.0 * 4096.0 * generations as f64) / start.elapsed().as_secs_f64()
+        }
+
+        let cells = soup(4096, 4096, 42);
+        let mut packed = PackedAutomaton::new(&cells, Rule::LIFE);
+        let packed_rate = rate(1000, |n| packed.steps(n));
+        println!(
+            "4096x4096, PackedAutomaton, 1000 generations: {packed_rate:.0} cells per second, {} alive",
+            packed.alive()
+        );
+
+        // The plain automaton is far slower, so it only runs a few generations of the same soup
+        let mut slow = Automaton::new(cells.clone(), Rule::LIFE);
+        let slow_rate = rate(10, |n| slow.steps(n));
+        let mut packed = PackedAutomaton::new(&cells, Rule::LIFE);
+        packed.steps(10);
+        assert_eq!(slow.alive(), packed.alive());
+        println!(
+            "4096x4096, Automaton, 10 generations: {slow_rate:.0} cells per second, {} alive",
+            slow.alive()
+        );
+        println!("Speedup: {:.1}x", packed_rate / slow_rate);
+    }
+}
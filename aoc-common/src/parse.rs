@@ -0,0 +1,271 @@
+//! Helpers for the input formats that keep coming back, reporting where the input did not match
+
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::{FromStr, SplitAsciiWhitespace};
+
+/// The non-empty lines of the input, trimmed so that indented examples parse the same as puzzle
+/// inputs
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim).filter(|l| !l.is_empty())
+}
+
+/// A part of the input that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the offending text, starting at 1
+    pub line: usize,
+    /// The column of the offending text in characters, starting at 1
+    pub column: usize,
+    /// The offending text, empty when the line ended too early
+    pub found: String,
+    /// A description of what should have been there
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found [{}]", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The puzzle input being parsed. Every `&str` handed to it must be a slice of the input, so that
+/// errors can point at the line and column it came from.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    /// The non-empty lines of the input, trimmed
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        lines(self.text)
+    }
+
+    /// An error pointing at `found`, a slice of the input
+    pub fn error(&self, found: &str, expected: impl Display) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (found.as_ptr() as usize).saturating_sub(start);
+        debug_assert!(
+            offset <= self.text.len(),
+            "[{found}] is not part of the input"
+        );
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// An error pointing just past the end of `s`, for when it ended too early
+    pub fn end_error(&self, s: &str, expected: impl Display) -> ParseError {
+        self.error(&s[s.len()..], expected)
+    }
+
+    /// Parse `s` into a `T`, describing it as `expected` if it is invalid
+    pub fn parse<T: FromStr>(&self, s: &str, expected: impl Display) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(s, expected))
+    }
+
+    /// Split `s` around the first occurrence of `delim`
+    pub fn split_once<'b>(
+        &self,
+        s: &'b str,
+        delim: &str,
+    ) -> Result<(&'b str, &'b str), ParseError> {
+        s.split_once(delim)
+            .ok_or_else(|| self.error(s, format!("[{delim}]")))
+    }
+
+    /// Remove `prefix` from the start of `s`
+    pub fn strip_prefix<'b>(&self, s: &'b str, prefix: &str) -> Result<&'b str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("[{prefix}]")))
+    }
+
+    /// Remove `suffix` from the end of `s`
+    pub fn strip_suffix<'b>(&self, s: &'b str, suffix: &str) -> Result<&'b str, ParseError> {
+        s.strip_suffix(suffix)
+            .ok_or_else(|| self.error(s, format!("[{suffix}]")))
+    }
+
+    /// Read `line` word by word
+    pub fn words(&self, line: &'a str) -> Words<'a> {
+        Words {
+            source: *self,
+            line,
+            iter: line.split_ascii_whitespace(),
+        }
+    }
+
+    /// Parse every item of `s` separated by `sep`
+    pub fn separated<T: FromStr>(&self, s: &'a str, sep: char) -> Result<Vec<T>, ParseError> {
+        s.trim()
+            .split(sep)
+            .map(|item| self.parse(item.trim(), type_name::<T>()))
+            .collect()
+    }
+
+    /// Parse a comma separated list such as `1,2,3`
+    pub fn comma_separated<T: FromStr>(&self, s: &'a str) -> Result<Vec<T>, ParseError> {
+        self.separated(s, ',')
+    }
+
+    /// Parse a whitespace separated list such as `1 2   3`
+    pub fn whitespace_separated<T: FromStr>(&self, s: &'a str) -> Result<Vec<T>, ParseError> {
+        s.split_whitespace()
+            .map(|item| self.parse(item, type_name::<T>()))
+            .collect()
+    }
+}
+
+/// The words of a line, failing with the position of the end of the line when one is missing
+pub struct Words<'a> {
+    source: Source<'a>,
+    line: &'a str,
+    iter: SplitAsciiWhitespace<'a>,
+}
+
+impl<'a> Words<'a> {
+    /// The next word, described as `expected` if the line ended
+    pub fn next(&mut self, expected: impl Display) -> Result<&'a str, ParseError> {
+        self.iter
+            .next()
+            .ok_or_else(|| self.source.end_error(self.line, expected))
+    }
+
+    /// Parse the next word into a `T`
+    pub fn parse<T: FromStr>(&mut self, expected: impl Display) -> Result<T, ParseError> {
+        let word = self.next(&expected)?;
+        self.source.parse(word, expected)
+    }
+
+    /// Check that the next word is `word`
+    pub fn expect(&mut self, word: &str) -> Result<(), ParseError> {
+        self.one_of(&[word]).map(drop)
+    }
+
+    /// The next word, which has to be one of `words`
+    pub fn one_of(&mut self, words: &[&str]) -> Result<&'a str, ParseError> {
+        let expected = words
+            .iter()
+            .map(|w| format!("[{w}]"))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        match self.next(&expected)? {
+            w if words.contains(&w) => Ok(w),
+            w => Err(self.source.error(w, expected)),
+        }
+    }
+
+    /// Skip the next `n` words, which only need to be there
+    pub fn skip(&mut self, n: usize) -> Result<(), ParseError> {
+        for _ in 0..n {
+            self.next("another word")?;
+        }
+        Ok(())
+    }
+
+    /// Check that no words are left on the line
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        match self.iter.next() {
+            Some(w) => Err(self.source.error(w, "end of line")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse every item of the input separated by `sep`
+pub fn separated<T: FromStr>(input: &str, sep: char) -> Result<Vec<T>, ParseError> {
+    Source::new(input).separated(input, sep)
+}
+
+/// Parse a comma separated list such as `1,2,3`
+pub fn comma_separated<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    Source::new(input).comma_separated(input)
+}
+
+/// Parse a whitespace separated list such as `1 2   3`
+pub fn whitespace_separated<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    Source::new(input).whitespace_separated(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separated() {
+        assert_eq!(comma_separated::<i64>("1,-2,3\n"), Ok(vec![1, -2, 3]));
+        assert_eq!(
+            whitespace_separated::<u32>(" 5 1   9\t5"),
+            Ok(vec![5, 1, 9, 5])
+        );
+        assert_eq!(lines("a\n   b\n\n").collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_error_position() {
+        let input = "1,2,3\n4,x,6";
+        let source = Source::new(input);
+        let line = source.lines().nth(1).unwrap();
+        let err = source.comma_separated::<u32>(line).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.to_string(), "line 2, column 3: expected u32, found [x]");
+    }
+
+    #[test]
+    fn test_words() {
+        let input = "value 5 goes to bot 2\r\nvalue 3 goes";
+        let source = Source::new(input);
+        let mut lines = source.lines();
+
+        let mut words = source.words(lines.next().unwrap());
+        words.expect("value").unwrap();
+        assert_eq!(words.parse::<u32>("a value"), Ok(5));
+        words.skip(3).unwrap();
+        assert_eq!(words.parse::<u32>("a bot"), Ok(2));
+
+        let mut words = source.words(lines.next().unwrap());
+        assert_eq!(
+            words.expect("bot").unwrap_err().to_string(),
+            "line 2, column 1: expected [bot], found [value]"
+        );
+        assert_eq!(
+            words.one_of(&["bot", "output"]).unwrap_err().expected,
+            "[bot] or [output]"
+        );
+        words.skip(1).unwrap();
+        assert_eq!(
+            words.next("[to]").unwrap_err().to_string(),
+            "line 2, column 13: expected [to], found end of line"
+        );
+        words.end().unwrap();
+
+        let mut words = source.words(input.lines().next().unwrap());
+        words.skip(5).unwrap();
+        assert_eq!(
+            words.end().unwrap_err().to_string(),
+            "line 1, column 21: expected end of line, found [2]"
+        );
+    }
+}
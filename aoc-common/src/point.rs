@@ -0,0 +1,91 @@
+use std::ops::{Add, Sub};
+
+use crate::Direction;
+
+/// A position on an unbounded grid, with y growing upwards
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Move a single step in a direction
+    #[must_use]
+    pub fn step(self, dir: Direction) -> Self {
+        let (dx, dy) = dir.offset();
+        Self::new(self.x + dx, self.y + dy)
+    }
+
+    /// Manhattan distance to the origin
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// Manhattan distance to another point
+    pub fn distance(self, other: Self) -> i32 {
+        (self - other).manhattan()
+    }
+
+    /// The four points sharing an edge with this one
+    pub fn orthogonal_neighbors(self) -> [Self; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// The eight points sharing an edge or a corner with this one
+    pub fn all_neighbors(self) -> [Self; 8] {
+        let Self { x, y } = self;
+        [
+            Self::new(x - 1, y - 1),
+            Self::new(x, y - 1),
+            Self::new(x + 1, y - 1),
+            Self::new(x - 1, y),
+            Self::new(x + 1, y),
+            Self::new(x - 1, y + 1),
+            Self::new(x, y + 1),
+            Self::new(x + 1, y + 1),
+        ]
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let p = Point::ORIGIN.step(Direction::Up).step(Direction::Right);
+        assert_eq!(p, Point::new(1, 1));
+        assert_eq!(p.distance(Point::new(-2, 3)), 5);
+        assert!(p.all_neighbors().contains(&Point::ORIGIN));
+        assert!(!p.orthogonal_neighbors().contains(&Point::ORIGIN));
+    }
+}
@@ -0,0 +1,485 @@
+//! Watch the simulations of the solutions, such as lights evolving or a screen being drawn.
+//!
+//! Frames are grids of palette indices, recorded into a [`Recording`] that is written as an animated
+//! GIF, as a sequence of PPM or PNG images, or played back in the terminal. Solutions record through
+//! a [`Recorder`], which does nothing unless the `AOC_RENDER` variable selects it, the way `AOC_LOG`
+//! selects what is logged: `AOC_RENDER=aoc2015::day18` writes a GIF of each part of that day, and
+//! `AOC_RENDER=aoc2016::day8=ansi,aoc2019=png` picks the format per target. Files are written to the
+//! directory in `AOC_RENDER_DIR`, `render` by default.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use log::{info, warn};
+
+use crate::Grid;
+
+/// The environment variable selecting the recorders, with their formats
+pub const ENV_VAR: &str = "AOC_RENDER";
+
+/// The environment variable holding the directory to write the recordings to
+pub const DIR_VAR: &str = "AOC_RENDER_DIR";
+
+pub type Rgb = [u8; 3];
+
+/// Off and on, in the colors of the site
+pub const MONOCHROME: &[Rgb] = &[[0x0f, 0x0f, 0x23], [0xff, 0xff, 0x66]];
+
+/// A picture of a grid, as one palette index per cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    /// A frame with the palette index `f(x, y)` at each cell
+    pub fn new(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> u8) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> u8) -> Self {
+        Self::new(grid.width(), grid.height(), |x, y| {
+            f(grid.get(x, y).expect("The frame has the size of the grid"))
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// How a recording is written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Ppm,
+    Png,
+    /// Played back in the terminal
+    Ansi,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Self::Gif),
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            "ansi" => Ok(Self::Ansi),
+            _ => Err(format!(
+                "Unknown format [{s}], expected gif, ppm, png or ansi"
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Gif => "gif",
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+            Self::Ansi => "ansi",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The frames of a simulation, with the colors of their palette indices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    palette: Vec<Rgb>,
+    frames: Vec<Frame>,
+    /// The side of the square of pixels drawn for each cell in images
+    scale: usize,
+    delay: Duration,
+}
+
+impl Recording {
+    /// An empty recording drawing each cell as 4x4 pixels, at 10 frames per second
+    pub fn new(palette: &[Rgb]) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "A palette has 1 to 256 colors"
+        );
+        Self {
+            palette: palette.to_vec(),
+            frames: vec![],
+            scale: 4,
+            delay: Duration::from_millis(100),
+        }
+    }
+
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        assert!(
+            frame
+                .pixels
+                .iter()
+                .all(|p| usize::from(*p) < self.palette.len()),
+            "Every pixel should be in the palette"
+        );
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// The pixels of a frame with each cell drawn as a square of `scale` pixels
+    fn scaled(&self, frame: &Frame) -> (usize, usize, Vec<u8>) {
+        let (width, height) = (frame.width * self.scale, frame.height * self.scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(frame.get(x / self.scale, y / self.scale));
+            }
+        }
+        (width, height, pixels)
+    }
+
+    /// Write the frames as an animated GIF, looping forever
+    pub fn write_gif(&self, out: impl Write) -> io::Result<()> {
+        let error = |e| io::Error::other(format!("GIF: {e}"));
+        let Some(first) = self.frames.first() else {
+            return Err(io::Error::other("GIF: there are no frames"));
+        };
+        let size = |n: usize| {
+            u16::try_from(n * self.scale)
+                .map_err(|_| io::Error::other("GIF: the frames are too large"))
+        };
+        // The palette of a GIF has a power of two colors
+        let mut palette: Vec<u8> = self.palette.iter().flatten().copied().collect();
+        palette.resize(self.palette.len().next_power_of_two().max(2) * 3, 0);
+
+        let mut encoder = gif::Encoder::new(out, size(first.width)?, size(first.height)?, &palette)
+            .map_err(error)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(error)?;
+        for frame in &self.frames {
+            let (width, height, pixels) = self.scaled(frame);
+            let mut frame = gif::Frame::from_indexed_pixels(
+                size(width / self.scale)?,
+                size(height / self.scale)?,
+                pixels,
+                None,
+            );
+            // In hundredths of a second
+            frame.delay = u16::try_from(self.delay.as_millis() / 10).unwrap_or(u16::MAX);
+            encoder.write_frame(&frame).map_err(error)?;
+        }
+        Ok(())
+    }
+
+    /// Write each frame to `dir` as `STEM-0001.EXT` and so on with `write`
+    fn write_sequence(
+        &self,
+        dir: &Path,
+        stem: &str,
+        extension: &str,
+        write: impl Fn(&Self, &Frame, &mut BufWriter<File>) -> io::Result<()>,
+    ) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+        let mut paths = vec![];
+        for (i, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("{stem}-{:04}.{extension}", i + 1));
+            let mut out = BufWriter::new(File::create(&path)?);
+            write(self, frame, &mut out)?;
+            out.flush()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn write_ppm_frame(&self, frame: &Frame, out: &mut impl Write) -> io::Result<()> {
+        let (width, height, pixels) = self.scaled(frame);
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        for p in pixels {
+            out.write_all(&self.palette[usize::from(p)])?;
+        }
+        Ok(())
+    }
+
+    fn write_png_frame(&self, frame: &Frame, out: &mut impl Write) -> io::Result<()> {
+        let (width, height, pixels) = self.scaled(frame);
+        let size = |n: usize| u32::try_from(n).map_err(|_| io::Error::other("PNG: too large"));
+        let mut encoder = png::Encoder::new(out, size(width)?, size(height)?);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette.iter().flatten().copied().collect::<Vec<_>>());
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&pixels).map_err(io::Error::other)
+    }
+
+    /// Write each frame as a PPM image, returning their paths
+    pub fn write_ppm(&self, dir: &Path, stem: &str) -> io::Result<Vec<PathBuf>> {
+        self.write_sequence(dir, stem, "ppm", |r, frame, out| {
+            r.write_ppm_frame(frame, out)
+        })
+    }
+
+    /// Write each frame as a PNG image, returning their paths
+    pub fn write_png(&self, dir: &Path, stem: &str) -> io::Result<Vec<PathBuf>> {
+        self.write_sequence(dir, stem, "png", |r, frame, out| {
+            r.write_png_frame(frame, out)
+        })
+    }
+
+    /// Play the frames back in a terminal with 24-bit colors, drawing two rows of cells per line
+    /// with half blocks and going back up over the previous frame with the cursor
+    pub fn play(&self, mut out: impl Write) -> io::Result<()> {
+        // Hide the cursor while playing
+        write!(out, "\x1b[?25l")?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let lines = frame.height.div_ceil(2);
+            if i > 0 {
+                write!(out, "\x1b[{lines}A")?;
+            }
+            for y in (0..frame.height).step_by(2) {
+                for x in 0..frame.width {
+                    let [r, g, b] = self.palette[usize::from(frame.get(x, y))];
+                    write!(out, "\x1b[38;2;{r};{g};{b}m")?;
+                    if y + 1 < frame.height {
+                        let [r, g, b] = self.palette[usize::from(frame.get(x, y + 1))];
+                        write!(out, "\x1b[48;2;{r};{g};{b}m")?;
+                    }
+                    write!(out, "▀")?;
+                }
+                writeln!(out, "\x1b[0m")?;
+            }
+            out.flush()?;
+            if i + 1 < self.frames.len() {
+                thread::sleep(self.delay);
+            }
+        }
+        write!(out, "\x1b[?25h")?;
+        out.flush()
+    }
+}
+
+/// The format `AOC_RENDER` selects for `name`, from its most specific target
+fn selected(spec: &str, name: &str) -> Option<Format> {
+    let mut best: Option<(usize, Format)> = None;
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (target, format) = match entry.split_once('=') {
+            Some((target, format)) => match format.parse() {
+                Ok(format) => (target, format),
+                Err(e) => {
+                    warn!("{ENV_VAR}: {e}");
+                    continue;
+                }
+            },
+            None => (entry, Format::Gif),
+        };
+        let matches = name == target
+            || name
+                .strip_prefix(target)
+                .is_some_and(|rest| rest.starts_with("::"));
+        if matches && best.is_none_or(|(len, _)| target.len() >= len) {
+            best = Some((target.len(), format));
+        }
+    }
+    best.map(|(_, format)| format)
+}
+
+/// Records the frames of a solution when `AOC_RENDER` selects it, and does nothing otherwise
+pub struct Recorder {
+    name: String,
+    format: Format,
+    recording: Option<Recording>,
+}
+
+impl Recorder {
+    /// A recorder named like a log target, e.g. `concat!(module_path!(), "::part1")`
+    pub fn from_env(name: &str, palette: &[Rgb]) -> Self {
+        let format = std::env::var(ENV_VAR)
+            .ok()
+            .and_then(|spec| selected(&spec, name));
+        Self {
+            name: name.to_string(),
+            format: format.unwrap_or(Format::Gif),
+            recording: format.map(|_| Recording::new(palette)),
+        }
+    }
+
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.recording = self.recording.map(|r| r.scale(scale));
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.recording = self.recording.map(|r| r.delay(delay));
+        self
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Record a frame, which is only drawn when recording
+    pub fn record(&mut self, frame: impl FnOnce() -> Frame) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(frame());
+        }
+    }
+
+    fn write(&self, recording: &Recording) -> io::Result<String> {
+        let dir = PathBuf::from(std::env::var(DIR_VAR).unwrap_or_else(|_| String::from("render")));
+        let stem = self.name.replace("::", "-");
+        let written = match self.format {
+            Format::Gif => {
+                fs::create_dir_all(&dir)?;
+                let path = dir.join(format!("{stem}.gif"));
+                recording.write_gif(BufWriter::new(File::create(&path)?))?;
+                path.display().to_string()
+            }
+            Format::Ppm => format!("{} images", recording.write_ppm(&dir, &stem)?.len()),
+            Format::Png => format!("{} images", recording.write_png(&dir, &stem)?.len()),
+            Format::Ansi => {
+                recording.play(io::stderr().lock())?;
+                String::from("the terminal")
+            }
+        };
+        Ok(written)
+    }
+
+    /// Write out the recording, logging where it went or why it could not be written
+    pub fn finish(self) {
+        let Some(recording) = &self.recording else {
+            return;
+        };
+        match self.write(recording) {
+            Ok(written) => info!(
+                "{}: {} frames rendered to {written}",
+                self.name,
+                recording.frames.len()
+            ),
+            Err(e) => warn!("{}: could not render as {}: {e}", self.name, self.format),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new(MONOCHROME).scale(2);
+        recording.push(Frame::new(3, 2, |x, y| u8::from(x == y)));
+        recording.push(Frame::from_grid(
+            &Grid::parse("#..\n###", |c| c == '#'),
+            |on| u8::from(*on),
+        ));
+        recording
+    }
+
+    #[test]
+    fn test_selected() {
+        let name = "aoc2015::day18::part1";
+        assert_eq!(selected("aoc2015::day18", name), Some(Format::Gif));
+        assert_eq!(
+            selected("aoc2015=png,aoc2015::day18=ansi", name),
+            Some(Format::Ansi)
+        );
+        assert_eq!(
+            selected("aoc2015::day18=ansi,aoc2015=png", name),
+            Some(Format::Ansi)
+        );
+        assert_eq!(selected("aoc2015::day1", name), None);
+        assert_eq!(selected("aoc2015=mp4", name), None);
+        assert_eq!(selected("", name), None);
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut gif = vec![];
+        recording().write_gif(&mut gif).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 4));
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&first.buffer[..6], &[1, 1, 0, 0, 0, 0]);
+        assert_eq!(first.delay, 10);
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&second.buffer[18..], &[1, 1, 1, 1, 1, 1]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sequences() {
+        let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let recording = recording();
+
+        let paths = recording.write_ppm(&dir, "lights").unwrap();
+        assert_eq!(
+            paths,
+            vec![dir.join("lights-0001.ppm"), dir.join("lights-0002.ppm")]
+        );
+        let ppm = fs::read(&paths[0]).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(&ppm[header.len()..header.len() + 3], &MONOCHROME[1]);
+
+        let paths = recording.write_png(&dir, "lights").unwrap();
+        let decoder = png::Decoder::new(io::BufReader::new(File::open(&paths[1]).unwrap()));
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (6, 4));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_play() {
+        let mut out = vec![];
+        recording().delay(Duration::ZERO).play(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // One line per two rows, going back up a line for the second frame
+        assert_eq!(out.matches('\n').count(), 2);
+        assert_eq!(out.matches("\x1b[1A").count(), 1);
+        assert_eq!(out.matches('▀').count(), 6);
+        assert!(out.ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_inactive_recorder() {
+        let mut recorder = Recorder::from_env("aoc1999::day1::part1", MONOCHROME);
+        assert!(!recorder.is_recording());
+        recorder.record(|| unreachable!("Frames are not drawn when not recording"));
+        recorder.finish();
+    }
+}
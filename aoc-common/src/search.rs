@@ -0,0 +1,102 @@
+//! Graph searches over implicit graphs, where the edges of a state are produced on demand
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Follow the recorded predecessors back from `end` to build the path leading to it
+fn path_to<S: Clone + Eq + Hash>(end: S, prev: &HashMap<S, Option<S>>) -> Vec<S> {
+    let mut path = vec![end];
+    while let Some(Some(p)) = prev.get(path.last().unwrap()) {
+        path.push(p.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search from `start` for the closest state satisfying `is_goal`.
+/// Returns the path from `start` to that state, both included.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut prev = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(path_to(state, &prev));
+        }
+        for next in neighbors(&state) {
+            if !prev.contains_key(&next) {
+                prev.insert(next.clone(), Some(state.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm from `start` for the cheapest state satisfying `is_goal`, where
+/// `neighbors` yields each next state along with the cost of reaching it.
+/// Returns the total cost and the path from `start` to that state, both included.
+pub fn dijkstra<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut best = HashMap::from([(start.clone(), 0)]);
+    let mut prev = HashMap::from([(start.clone(), None)]);
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        if best.get(&state).is_some_and(|b| *b < cost) {
+            // Already reached more cheaply
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, path_to(state, &prev)));
+        }
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|b| next_cost < *b) {
+                best.insert(next.clone(), next_cost);
+                prev.insert(next.clone(), Some(state.clone()));
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs() {
+        // Reach 10 from 1 by doubling or adding one
+        let path = bfs(1, |n| [n * 2, n + 1], |n| *n == 10).unwrap();
+        assert_eq!(path, vec![1, 2, 4, 5, 10]);
+        assert_eq!(bfs(1, |n| [(n + 1) % 5], |n| *n == 7), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Doubling costs 2, adding one costs 1
+        let (cost, path) = dijkstra(1, |n| [(n * 2, 2), (n + 1, 1)], |n| *n == 16).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path, vec![1, 2, 4, 8, 16]);
+    }
+}
@@ -1,225 +1,150 @@
-//use std::fmt::Display;
-
+use aoc_common::parse::ParseError;
+use aoc_common::render::{Frame, Recorder, MONOCHROME};
+use aoc_common::{Automaton, Grid, PackedAutomaton, Rule};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-#[derive(Clone, Copy)]
-struct Light {
-    on: bool,
-}
-
-#[derive(Clone)]
-struct Grid {
+#[derive(Debug, Clone)]
+struct Lights {
     steps: usize,
-    line_len: usize,
-    vec: Vec<Light>,
+    grid: Grid<bool>,
 }
 
-//impl Display for Grid {
-//    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//        for line in self.vec.chunks(self.line_len) {
-//            for light in line {
-//                if light.on {
-//                    write!(f, "#")?;
-//                } else {
-//                    write!(f, ".")?;
-//                }
-//            }
-//            writeln!(f)?;
-//        }
-//        Ok(())
-//    }
-//}
-
 #[aoc_generator(day18)]
-fn parse(input: &str) -> Grid {
-    let mut lines = input.lines().peekable();
-    let line_len = lines.peek().unwrap().len();
-    let vec = lines
-        .flat_map(|line| {
-            line.trim().chars().map(|c| {
-                let on = c == '#';
-                Light { on }
-            })
-        })
-        .collect();
-    Grid {
-        line_len,
-        vec,
-        steps: 100,
-    }
+fn parse(input: &str) -> Result<Lights, ParseError> {
+    let grid = Grid::try_parse(
+        input,
+        |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "[#] or [.]",
+    )?;
+    Ok(Lights { grid, steps: 100 })
 }
 
-impl Grid {
-    /// Get the index of the top left corner of the grid
-    #[allow(clippy::unused_self)]
-    fn top_left_corner(&self) -> usize {
-        0
-    }
-
-    /// Get the index of the top right corner of the grid
-    fn top_right_corner(&self) -> usize {
-        self.line_len - 1
-    }
-
-    /// Get the index of the bottom left corner of the grid
-    fn bottom_left_corner(&self) -> usize {
-        // Calculate the number of rows
-        let number_of_rows = self.vec.len() / self.line_len;
-        // Calculate the starting index of the last row
-        (number_of_rows - 1) * self.line_len
-    }
-
-    /// Get the index of the bottom right corner of the grid
-    fn bottom_right_corner(&self) -> usize {
-        self.vec.len() - 1
-    }
-
-    /// Get neighbors of the given light index
-    fn get_neighbors(&self, index: usize) -> Vec<Light> {
-        assert!(index <= self.vec.len());
-
-        // Check if index is along the borders of the grid
-        let is_top = index <= self.top_right_corner();
-        let is_bottom = index >= self.bottom_left_corner();
-        let is_left = index % self.line_len == 0;
-        let is_right = (index % self.line_len) == (self.line_len - 1);
-
-        // Add neighbors to vec
-        let mut neighbors = Vec::with_capacity(8);
-        if !is_top {
-            if !is_left {
-                neighbors.push(self.vec[index - self.line_len - 1]);
-            }
-            neighbors.push(self.vec[index - self.line_len]);
-            if !is_right {
-                neighbors.push(self.vec[index - self.line_len + 1]);
-            }
-        }
-        if !is_left {
-            neighbors.push(self.vec[index - 1]);
-        }
-        if !is_right {
-            neighbors.push(self.vec[index + 1]);
-        }
-        if !is_bottom {
-            if !is_left {
-                neighbors.push(self.vec[index + self.line_len - 1]);
-            }
-            neighbors.push(self.vec[index + self.line_len]);
-            if !is_right {
-                neighbors.push(self.vec[index + self.line_len + 1]);
-            }
-        }
-
-        neighbors
-    }
-
-    fn step(&mut self) {
-        let mut new_grid = Vec::with_capacity(self.vec.len());
-        for (i, light) in self.vec.iter().enumerate() {
-            // get count of lit neighbors
-            let lit_neighbors = self.get_neighbors(i).iter().filter(|l| l.on).count();
-            let on = if light.on {
-                // stay on if count is 2 or 3
-                lit_neighbors == 2 || lit_neighbors == 3
-            } else {
-                // turn on if count == 3
-                lit_neighbors == 3
-            };
-            // push new value into new vec
-            new_grid.push(Light { on });
-        }
-
-        // set new vec to self.vec
-        self.vec = new_grid;
+/// Step the lights, recording each generation when `AOC_RENDER` selects `name`
+fn animate(automaton: &mut Automaton, steps: usize, name: &str) {
+    let mut recorder = Recorder::from_env(name, MONOCHROME);
+    let frame = |automaton: &Automaton| Frame::from_grid(automaton.cells(), |on| u8::from(*on));
+    recorder.record(|| frame(automaton));
+    for _ in 0..steps {
+        automaton.step();
+        recorder.record(|| frame(automaton));
     }
+    recorder.finish();
+}
 
-    /// Step 2, corners always stay on
-    fn step_with_fixed_corners(&mut self) {
-        let mut new_grid = Vec::with_capacity(self.vec.len());
-
-        let fixed_corners = [
-            self.top_left_corner(),
-            self.top_right_corner(),
-            self.bottom_left_corner(),
-            self.bottom_right_corner(),
-        ];
-
-        for (i, light) in self.vec.iter().enumerate() {
-            // get count of lit neighbors
-            let lit_neighbors = self.get_neighbors(i).iter().filter(|l| l.on).count();
-            let on = if fixed_corners.contains(&i) {
-                true
-            } else if light.on {
-                // stay on if count is 2 or 3
-                lit_neighbors == 2 || lit_neighbors == 3
-            } else {
-                // turn on if count == 3
-                lit_neighbors == 3
-            };
-            // push new value into new vec
-            new_grid.push(Light { on });
-        }
-
-        // set new vec to self.vec
-        self.vec = new_grid;
-    }
+#[aoc(day18, part1)]
+fn part1(lights: &Lights) -> usize {
+    let mut automaton = Automaton::new(lights.grid.clone(), Rule::LIFE);
+    animate(
+        &mut automaton,
+        lights.steps,
+        concat!(module_path!(), "::part1"),
+    );
+    automaton.alive()
+}
 
-    fn num_lit(&self) -> usize {
-        self.vec.iter().filter(|l| l.on).count()
+/// The corners are stuck on
+#[aoc(day18, part2)]
+fn part2(lights: &Lights) -> usize {
+    let mut automaton = Automaton::new(lights.grid.clone(), Rule::LIFE);
+    for corner in lights.grid.corners() {
+        automaton = automaton.fix(corner, true);
     }
+    animate(
+        &mut automaton,
+        lights.steps,
+        concat!(module_path!(), "::part2"),
+    );
+    automaton.alive()
 }
 
-#[aoc(day18, part1)]
-fn part1(grid: &Grid) -> usize {
-    let mut grid = grid.clone();
-    for _ in 0..grid.steps {
-        grid.step();
-    }
-    grid.num_lit()
+#[aoc(day18, part1, Packed)]
+fn part1_packed(lights: &Lights) -> usize {
+    let mut automaton = PackedAutomaton::new(&lights.grid, Rule::LIFE);
+    automaton.steps(lights.steps);
+    automaton.alive()
 }
 
-#[aoc(day18, part2)]
-fn part2(grid: &Grid) -> usize {
-    let mut grid = grid.clone();
-    for _ in 0..grid.steps {
-        //eprintln!("{grid}");
-        grid.step_with_fixed_corners();
+#[aoc(day18, part2, Packed)]
+fn part2_packed(lights: &Lights) -> usize {
+    let mut automaton = PackedAutomaton::new(&lights.grid, Rule::LIFE);
+    for corner in lights.grid.corners() {
+        automaton = automaton.fix(corner, true);
     }
-    grid.num_lit()
+    automaton.steps(lights.steps);
+    automaton.alive()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = ".#.#.#
+                           ...##.
+                           #....#
+                           ..#...
+                           #.#..#
+                           ####..";
+
+    const EXAMPLE_STUCK: &str = "##.#.#
+                                 ...##.
+                                 #....#
+                                 ..#...
+                                 #.#..#
+                                 ####.#";
+
     #[test]
     fn part1_example() {
-        let mut grid = parse(
-            ".#.#.#
-            ...##.
-            #....#
-            ..#...
-            #.#..#
-            ####..",
-        );
+        let mut grid = parse(EXAMPLE).unwrap();
         grid.steps = 4;
 
         assert_eq!(part1(&grid), 4);
+        assert_eq!(part1_packed(&grid), 4);
     }
 
     #[test]
     fn part2_example() {
-        let mut grid = parse(
-            "##.#.#
-            ...##.
-            #....#
-            ..#...
-            #.#..#
-            ####.#",
-        );
+        let mut grid = parse(EXAMPLE_STUCK).unwrap();
         grid.steps = 5;
 
         assert_eq!(part2(&grid), 17);
+        assert_eq!(part2_packed(&grid), 17);
+    }
+
+    #[test]
+    fn packed_matches_every_generation() {
+        for (example, stuck) in [(EXAMPLE, false), (EXAMPLE_STUCK, true)] {
+            let grid = parse(example).unwrap().grid;
+            let mut automaton = Automaton::new(grid.clone(), Rule::LIFE);
+            let mut packed = PackedAutomaton::new(&grid, Rule::LIFE);
+            if stuck {
+                for corner in grid.corners() {
+                    automaton = automaton.fix(corner, true);
+                    packed = packed.fix(corner, true);
+                }
+            }
+            for _ in 0..5 {
+                automaton.step();
+                packed.step();
+                assert_eq!(packed.to_grid(), *automaton.cells(), "\n{automaton}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse(".#.\n#.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a row of 3 cells, found [#.]"
+        );
+        let err = parse(".#.\n#o.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected [#] or [.], found [o]"
+        );
     }
 }
@@ -1,6 +1,11 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 
+use aoc_common::cancel::checkpoint;
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::{debug, trace};
 
 #[derive(Clone)]
 struct Replacement {
@@ -14,26 +19,466 @@ struct Machine {
 }
 
 #[aoc_generator(day19)]
-fn parse(input: &str) -> Machine {
-    let (rs, m) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<Machine, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    // The replacements come first, then the molecule on the last line
+    let lines: Vec<_> = source.lines().collect();
+    let Some((molecule, rs)) = lines.split_last() else {
+        return Err(source.end_error(input, "a molecule"));
+    };
 
     let replacements = rs
-        .lines()
+        .iter()
         .map(|l| {
-            let (f, t) = l.trim().split_once(" => ").unwrap();
-            Replacement {
+            if l.ends_with("=>") {
+                return Err(source.end_error(l, "a molecule to replace with"));
+            }
+            let (f, t) = source.split_once(l, " => ")?;
+            Ok(Replacement {
                 from: f.to_string(),
                 to: t.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Machine {
+    Ok(Machine {
         replacements,
-        molecule: m.trim().to_string(),
+        molecule: molecule.to_string(),
+    })
+}
+
+/// The elements of a molecule, each a capital letter followed by lowercase letters, or `e`
+fn elements(molecule: &str) -> Vec<&str> {
+    let mut elements = vec![];
+    let mut start = 0;
+    for (i, c) in molecule.char_indices().skip(1) {
+        if !c.is_ascii_lowercase() {
+            elements.push(&molecule[start..i]);
+            start = i;
+        }
+    }
+    if !molecule.is_empty() {
+        elements.push(&molecule[start..]);
+    }
+    elements
+}
+
+/// An element, or a prefix of the right-hand side of a replacement
+type Symbol = usize;
+
+/// A replacement of an element by elements
+type Rule = (Symbol, Vec<Symbol>);
+
+/// The elements met so far, numbered in order of appearance
+#[derive(Debug, Clone, Default)]
+struct Elements {
+    symbols: HashMap<String, Symbol>,
+    names: Vec<String>,
+}
+
+impl Elements {
+    fn intern(&mut self, element: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(element) {
+            return *symbol;
+        }
+        self.names.push(element.to_string());
+        self.symbols
+            .insert(element.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    fn get(&self, element: &str) -> Option<Symbol> {
+        self.symbols.get(element).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    fn molecule(&mut self, molecule: &str) -> Vec<Symbol> {
+        elements(molecule)
+            .into_iter()
+            .map(|e| self.intern(e))
+            .collect()
+    }
+
+    fn rules(&mut self, replacements: &[Replacement]) -> Vec<Rule> {
+        replacements
+            .iter()
+            .map(|r| (self.intern(&r.from), self.molecule(&r.to)))
+            .collect()
+    }
+
+    /// A molecule written out
+    fn spell(&self, molecule: impl IntoIterator<Item = Symbol>) -> String {
+        molecule
+            .into_iter()
+            .map(|s| self.names[s].as_str())
+            .collect()
     }
 }
 
+/// The replacements as a grammar whose rules have one or two symbols on the right, replacements
+/// of more than two elements being split into rules building up their prefixes
+struct Grammar {
+    /// The elements, the symbols of the prefixes coming after them
+    elements: Elements,
+    /// The symbols made of a single one, like `e => H`, each a step
+    unary: Vec<(Symbol, Symbol)>,
+    /// The symbols made of two, with the steps it takes: 1 for a replacement, 0 for a prefix
+    binary: HashMap<(Symbol, Symbol), Vec<(Symbol, u32)>>,
+}
+
+impl Grammar {
+    fn new(replacements: &[Replacement]) -> Self {
+        let mut elements = Elements::default();
+        let rules = elements.rules(replacements);
+        let mut grammar = Self {
+            unary: vec![],
+            binary: HashMap::new(),
+            elements,
+        };
+        let mut prefixes: HashMap<(Symbol, Symbol), Symbol> = HashMap::new();
+        let mut next = grammar.elements.len();
+        for (from, to) in rules {
+            let (last, init) = to
+                .split_last()
+                .expect("The generator rejects empty replacements");
+            let Some((first, middle)) = init.split_first() else {
+                grammar.unary.push((from, *last));
+                continue;
+            };
+            let mut prefix = *first;
+            for symbol in middle {
+                prefix = *prefixes.entry((prefix, *symbol)).or_insert_with(|| {
+                    grammar
+                        .binary
+                        .entry((prefix, *symbol))
+                        .or_default()
+                        .push((next, 0));
+                    next += 1;
+                    next - 1
+                });
+            }
+            grammar
+                .binary
+                .entry((prefix, *last))
+                .or_default()
+                .push((from, 1));
+        }
+        grammar
+    }
+
+    fn is_element(&self, symbol: Symbol) -> bool {
+        symbol < self.elements.len()
+    }
+
+    /// Add the symbols a cell makes through unary rules
+    fn close(&self, mut cell: Cell) -> Cell {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (from, to) in &self.unary {
+                if let Some((cost, _)) = cell.get(to).copied() {
+                    changed |= relax(&mut cell, *from, cost + 1, Back::Unary(*to));
+                }
+            }
+        }
+        cell
+    }
+}
+
+/// How the cheapest way to make a symbol over a span of the molecule was found
+#[derive(Debug, Clone, Copy)]
+enum Back {
+    Element,
+    Unary(Symbol),
+    /// The two symbols, and where the second one starts
+    Binary(Symbol, Symbol, usize),
+}
+
+/// The cheapest ways to make each symbol over a span of the molecule, ordered so that ties are
+/// broken the same way on every run
+type Cell = BTreeMap<Symbol, (u32, Back)>;
+
+/// A replacement applied to an element, with the elements it made
+struct Node {
+    element: Symbol,
+    children: Vec<Node>,
+}
+
+/// The molecules from the start to the target, one replacement at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Derivation {
+    molecules: Vec<String>,
+}
+
+impl Derivation {
+    fn steps(&self) -> usize {
+        self.molecules.len() - 1
+    }
+}
+
+impl Display for Derivation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.molecules.join(" => "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Underivable {
+    /// An element that no replacement makes
+    UnknownElement(String),
+    NoDerivation {
+        start: String,
+        target: String,
+    },
+}
+
+impl Error for Underivable {}
+
+impl Display for Underivable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownElement(element) => {
+                write!(f, "No replacement makes the element [{element}]")
+            }
+            Self::NoDerivation { start, target } => {
+                write!(f, "No replacements turn [{start}] into [{target}]")
+            }
+        }
+    }
+}
+
+/// The table of a CYK parse of the molecule, indexed by the length and then the start of a span
+struct Parse<'a> {
+    grammar: &'a Grammar,
+    table: Vec<Vec<Cell>>,
+}
+
+impl<'a> Parse<'a> {
+    fn new(grammar: &'a Grammar, molecule: &[Symbol]) -> Self {
+        let leaves = molecule
+            .iter()
+            .map(|s| grammar.close(Cell::from([(*s, (0, Back::Element))])))
+            .collect();
+        let mut table: Vec<Vec<Cell>> = vec![leaves];
+        for len in 2..=molecule.len() {
+            checkpoint();
+            let row = (0..=molecule.len() - len)
+                .map(|start| {
+                    let mut cell = Cell::new();
+                    for mid in start + 1..start + len {
+                        let left = &table[mid - start - 1][start];
+                        let right = &table[start + len - mid - 1][mid];
+                        for (b, (b_cost, _)) in left {
+                            for (c, (c_cost, _)) in right {
+                                for (a, step) in grammar.binary.get(&(*b, *c)).into_iter().flatten()
+                                {
+                                    let back = Back::Binary(*b, *c, mid);
+                                    relax(&mut cell, *a, b_cost + c_cost + step, back);
+                                }
+                            }
+                        }
+                    }
+                    grammar.close(cell)
+                })
+                .collect();
+            table.push(row);
+        }
+        Self { grammar, table }
+    }
+
+    fn cell(&self, start: usize, end: usize) -> &Cell {
+        &self.table[end - start - 1][start]
+    }
+
+    /// The replacements making `symbol` over a span, with prefixes flattened into their rule
+    fn tree(&self, symbol: Symbol, start: usize, end: usize) -> Node {
+        let mut children = vec![];
+        match self.cell(start, end)[&symbol].1 {
+            Back::Element => {}
+            Back::Unary(child) => children.push(self.tree(child, start, end)),
+            Back::Binary(..) => self.flatten(symbol, start, end, &mut children),
+        }
+        Node {
+            element: symbol,
+            children,
+        }
+    }
+
+    fn flatten(&self, symbol: Symbol, start: usize, end: usize, children: &mut Vec<Node>) {
+        let Back::Binary(b, c, mid) = self.cell(start, end)[&symbol].1 else {
+            unreachable!("Only pairs are flattened");
+        };
+        if self.grammar.is_element(b) {
+            children.push(self.tree(b, start, mid));
+        } else {
+            self.flatten(b, start, mid, children);
+        }
+        children.push(self.tree(c, mid, end));
+    }
+}
+
+/// Record a way to make `symbol` if it is cheaper than the known ones
+fn relax(cell: &mut Cell, symbol: Symbol, cost: u32, back: Back) -> bool {
+    if cell.get(&symbol).is_some_and(|(c, _)| *c <= cost) {
+        return false;
+    }
+    cell.insert(symbol, (cost, back));
+    true
+}
+
+/// The shortest derivation of the molecule from `start`
+fn derive(m: &Machine, start: &str) -> Result<Derivation, Underivable> {
+    let grammar = Grammar::new(&m.replacements);
+    let molecule = elements(&m.molecule)
+        .into_iter()
+        .map(|e| {
+            grammar
+                .elements
+                .get(e)
+                .ok_or_else(|| Underivable::UnknownElement(e.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let no_derivation = || Underivable::NoDerivation {
+        start: start.to_string(),
+        target: m.molecule.clone(),
+    };
+    let start_symbol = grammar.elements.get(start).ok_or_else(no_derivation)?;
+    if molecule.is_empty() {
+        return Err(no_derivation());
+    }
+
+    let parse = Parse::new(&grammar, &molecule);
+    let Some((steps, _)) = parse.cell(0, molecule.len()).get(&start_symbol) else {
+        return Err(no_derivation());
+    };
+    let tree = parse.tree(start_symbol, 0, molecule.len());
+
+    // Replace the leftmost element that gets replaced, one replacement at a time
+    let mut frontier = vec![&tree];
+    let mut molecules = vec![start.to_string()];
+    while let Some(i) = frontier.iter().position(|n| !n.children.is_empty()) {
+        let node = frontier[i];
+        frontier.splice(i..=i, &node.children);
+        molecules.push(grammar.elements.spell(frontier.iter().map(|n| n.element)));
+    }
+    debug_assert_eq!(molecules.len() - 1, *steps as usize);
+    Ok(Derivation { molecules })
+}
+
+/// A replacement made at a position of a molecule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Site {
+    at: usize,
+    rule: usize,
+}
+
+/// The distinct molecules one replacement away from a molecule, each known by a site that makes
+/// it. The molecules are never built: a replacement that starts with the element it replaces makes
+/// the same molecule as the replacement one element to the right, so each site is moved right as
+/// far as it goes, and sites end up at the same place with the same elements exactly when they
+/// make the same molecule.
+struct Calibration<'a> {
+    rules: &'a [Rule],
+    molecule: &'a [Symbol],
+    sites: Vec<Site>,
+}
+
+impl<'a> Calibration<'a> {
+    fn new(rules: &'a [Rule], molecule: &'a [Symbol]) -> Self {
+        let n = molecule.len();
+        // Past a site whose replacement starts like the molecule, the new molecule is the old one
+        // shifted by the length of the replacement less one, or by one for a replacement by
+        // nothing. For each such distance, how many elements from each position on are the same
+        // as the one that far ahead.
+        let distance = |to: &[Symbol]| to.len().abs_diff(1);
+        let mut runs: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (_, to) in rules {
+            runs.entry(distance(to)).or_insert_with_key(|d| {
+                let mut run = vec![0; n + 1];
+                for k in (0..n).rev() {
+                    if *d == 0 || (k + d < n && molecule[k] == molecule[k + d]) {
+                        run[k] = run[k + 1] + 1;
+                    }
+                }
+                run
+            });
+        }
+
+        let mut calibration = Self {
+            rules,
+            molecule,
+            sites: vec![],
+        };
+        let mut seen = HashSet::new();
+        for (at, element) in molecule.iter().enumerate() {
+            for (rule, (from, to)) in rules.iter().enumerate() {
+                if from != element {
+                    continue;
+                }
+                let site = Site { at, rule };
+                // How far the new molecule keeps to the old one from the site on
+                let common = match to.iter().zip(&molecule[at..]).position(|(a, b)| a != b) {
+                    Some(common) => common,
+                    None if to.len() <= n - at => {
+                        to.len() + runs[&distance(to)][at + to.len().min(1)]
+                    }
+                    None => n - at,
+                };
+                let moved = at + common.min(n - 1 - at);
+                let key: Vec<_> = (moved..moved + to.len())
+                    .map(|k| calibration.element(site, k))
+                    .collect();
+                if seen.insert((moved, key)) {
+                    calibration.sites.push(site);
+                }
+            }
+        }
+        calibration
+    }
+
+    fn len(&self) -> usize {
+        self.sites.len()
+    }
+
+    /// The element at `k` of the molecule made by a replacement
+    fn element(&self, site: Site, k: usize) -> Symbol {
+        let to = &self.rules[site.rule].1;
+        if k < site.at {
+            self.molecule[k]
+        } else if k < site.at + to.len() {
+            to[k - site.at]
+        } else {
+            self.molecule[k + 1 - to.len()]
+        }
+    }
+
+    /// The molecules, written out
+    fn molecules(&self, elements: &Elements) -> HashSet<String> {
+        let len = |site: Site| self.molecule.len() - 1 + self.rules[site.rule].1.len();
+        self.sites
+            .iter()
+            .map(|site| elements.spell((0..len(*site)).map(|k| self.element(*site, k))))
+            .collect()
+    }
+}
+
+/// The distinct molecules, told apart by their elements rather than as strings
+#[aoc(day19, part1)]
+fn part1(m: &Machine) -> usize {
+    let mut elements = Elements::default();
+    let rules = elements.rules(&m.replacements);
+    let molecule = elements.molecule(&m.molecule);
+    let calibration = Calibration::new(&rules, &molecule);
+    if log::log_enabled!(log::Level::Trace) {
+        trace!("{:?}", calibration.molecules(&elements));
+    }
+    calibration.len()
+}
+
 #[aoc(day19, part1, Vec)]
 fn part1_vec(m: &Machine) -> usize {
     let mut molecules = Vec::with_capacity(m.replacements.len());
@@ -85,6 +530,7 @@ fn part2_naive(m: &Machine) -> usize {
 
     // for each molecule path in queue
     while let Some(mp) = paths.pop_front() {
+        checkpoint();
         // --- DEBUG
         //eprintln!("{}", mp.join(" -> "));
         // --- DEBUG
@@ -141,6 +587,7 @@ fn part2_backwards(m: &Machine) -> usize {
     let mut paths = VecDeque::from([vec![m.molecule.clone()]]);
     // for each molecule path in queue
     while let Some(mp) = paths.pop_front() {
+        checkpoint();
         // --- DEBUG
         //eprintln!("{}", mp.join(" <- "));
         // --- DEBUG
@@ -183,6 +630,14 @@ fn part2_backwards(m: &Machine) -> usize {
     0
 }
 
+/// The fewest replacements, found by parsing the molecule with the replacements as a grammar
+#[aoc(day19, part2)]
+fn part2(m: &Machine) -> Result<usize, Underivable> {
+    let derivation = derive(m, "e")?;
+    debug!("{derivation}");
+    Ok(derivation.steps())
+}
+
 /// Simple solution replacing from the right instead of the left. Who knew??
 /// Ran the python implementation thru ChatGPT
 /// Source:
@@ -191,9 +646,11 @@ fn part2_backwards(m: &Machine) -> usize {
 /// this on my own, but couldn't get anywhere as I'd gotten
 /// myself so confused by all my optimizations.
 #[aoc(day19, part2, plagiarized)]
-fn part2_plagialized(m: &Machine) -> usize {
+fn part2_plagialized(m: &Machine) -> Option<usize> {
     let mut molecule = m.molecule.clone();
-    let reps: HashMap<_, _> = m
+    // Replacements are tried in the order of the input, as iterating over a map would make the
+    // number of steps change from one run to the next
+    let reps: Vec<_> = m
         .replacements
         .iter()
         .map(|r| (r.to.clone(), r.from.clone()))
@@ -203,6 +660,10 @@ fn part2_plagialized(m: &Machine) -> usize {
         let mut replaced = false;
 
         for (from, to) in &reps {
+            // `e` only ever stands alone, so it can only replace the whole molecule
+            if to == "e" && molecule != *from {
+                continue;
+            }
             // Find the first occurrence of `from` in `molecule`
             if let Some(pos) = molecule.find(from) {
                 // Replace the first occurrence
@@ -213,12 +674,12 @@ fn part2_plagialized(m: &Machine) -> usize {
             }
         }
 
-        // If no replacement was made, we should break to avoid an infinite loop
+        // If no replacement was made, the molecule cannot be made from `e` this way
         if !replaced {
-            break;
+            return None;
         }
     }
-    count
+    Some(count)
 }
 
 #[cfg(test)]
@@ -227,7 +688,7 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        for f in [part1_vec, part1_hash_set] {
+        for f in [part1, part1_vec, part1_hash_set] {
             assert_eq!(
                 f(&parse(
                     "H => HO
@@ -235,7 +696,8 @@ mod tests {
                      O => HH
 
                      HOH"
-                )),
+                )
+                .unwrap()),
                 4
             );
             assert_eq!(
@@ -245,7 +707,8 @@ mod tests {
                      O => HH
 
                      HOHOHO"
-                )),
+                )
+                .unwrap()),
                 7
             );
         }
@@ -253,7 +716,12 @@ mod tests {
 
     #[test]
     fn part2_example() {
-        for f in [part2_naive, part2_backwards, part2_plagialized] {
+        for f in [
+            |m: &Machine| part2(m).unwrap(),
+            part2_naive,
+            part2_backwards,
+            |m: &Machine| part2_plagialized(m).unwrap(),
+        ] {
             assert_eq!(
                 f(&parse(
                     "e => H
@@ -263,7 +731,8 @@ mod tests {
                      O => HH
 
                      HOH"
-                )),
+                )
+                .unwrap()),
                 3
             );
             assert_eq!(
@@ -275,9 +744,139 @@ mod tests {
                      O => HH
 
                      HOHOHO"
-                )),
+                )
+                .unwrap()),
                 6
             );
         }
     }
+
+    #[test]
+    fn test_parse_empty_replacement() {
+        let err = parse("e => H\nH =>\nHH").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: expected a molecule to replace with, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_elements() {
+        assert_eq!(
+            elements("CRnCaSiRnBSiRnFAr"),
+            ["C", "Rn", "Ca", "Si", "Rn", "B", "Si", "Rn", "F", "Ar"]
+        );
+        assert_eq!(elements("e"), ["e"]);
+        assert_eq!(elements(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_derivation() {
+        let machine = parse(
+            "e => H
+             e => O
+             H => HO
+             H => OH
+             O => HH
+
+             HOHOHO",
+        )
+        .unwrap();
+        let derivation = derive(&machine, "e").unwrap();
+        assert_eq!(derivation.steps(), 6);
+        assert_eq!(derivation.molecules.first().unwrap(), "e");
+        assert_eq!(derivation.molecules.last().unwrap(), "HOHOHO");
+
+        // Rn and Ar are only ever made, never replaced
+        let machine = parse(
+            "e => NRnAr
+             N => CRnAr
+             C => CC
+
+             CRnArRnAr",
+        )
+        .unwrap();
+        assert_eq!(
+            derive(&machine, "e").unwrap().to_string(),
+            "e => NRnAr => CRnArRnAr"
+        );
+    }
+
+    #[test]
+    fn test_underivable() {
+        let machine = parse("e => H\nH => HO\n\nOH").unwrap();
+        assert_eq!(
+            derive(&machine, "e").unwrap_err().to_string(),
+            "No replacements turn [e] into [OH]"
+        );
+        let machine = parse("e => H\nH => HO\n\nHX").unwrap();
+        assert_eq!(
+            derive(&machine, "e"),
+            Err(Underivable::UnknownElement(String::from("X")))
+        );
+    }
+
+    /// Replacements and molecules of elements none of which starts another, so that replacing
+    /// strings and replacing elements make the same molecules
+    fn machine(seed: u64, molecule_len: usize) -> Machine {
+        const ELEMENTS: [&str; 5] = ["H", "O", "Ca", "Si", "Rn"];
+        let mut state = seed;
+        let mut random = |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % n
+        };
+        // A molecule of `min` to `max` elements
+        let mut molecule = |min: usize, max: usize| {
+            let len = min + random(max - min + 1);
+            (0..len)
+                .map(|_| ELEMENTS[random(ELEMENTS.len())])
+                .collect::<String>()
+        };
+        let replacements = (0..8)
+            .map(|_| Replacement {
+                from: molecule(1, 1),
+                to: molecule(0, 3),
+            })
+            .collect();
+        Machine {
+            replacements,
+            molecule: molecule(molecule_len, molecule_len),
+        }
+    }
+
+    #[test]
+    fn part1_matches_strings() {
+        for seed in 1..50 {
+            let m = machine(seed, seed as usize);
+            let expected: HashSet<String> = m
+                .replacements
+                .iter()
+                .flat_map(|r| {
+                    m.molecule.match_indices(&r.from).map(|(i, _)| {
+                        let mut molecule = m.molecule.clone();
+                        molecule.replace_range(i..i + r.from.len(), &r.to);
+                        molecule
+                    })
+                })
+                .collect();
+
+            let mut elements = Elements::default();
+            let rules = elements.rules(&m.replacements);
+            let molecule = elements.molecule(&m.molecule);
+            let calibration = Calibration::new(&rules, &molecule);
+            assert_eq!(calibration.molecules(&elements), expected, "seed {seed}");
+            assert_eq!(part1(&m), expected.len());
+            assert_eq!(part1_vec(&m), expected.len());
+            assert_eq!(part1_hash_set(&m), expected.len());
+        }
+    }
+
+    #[test]
+    fn part1_long_molecule() {
+        // A replacement anywhere in a run of one element makes the same molecule
+        let m = parse(&format!("H => HH\nH => OH\n{}", "H".repeat(100_000))).unwrap();
+        assert_eq!(part1(&m), 1 + 100_000);
+    }
 }
@@ -1,78 +1,55 @@
+use aoc_common::cancel::checkpoint;
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 type Presents = u32;
 type HouseNumber = u32;
 
 #[aoc_generator(day20)]
-fn parse(input: &str) -> Presents {
-    input.parse().unwrap()
+fn parse(input: &str) -> Result<Presents, ParseError> {
+    aoc_common::logging::init();
+    Source::new(input).parse(input.trim(), "a number of presents")
 }
 
-#[aoc(day20, part1)]
-fn part1(input: &Presents) -> HouseNumber {
-    // house numbers
-    (1..)
-        .map(|hn| {
-            // elves bearing presents
-            let prs: Presents = (1..=hn).filter(|e| hn % e == 0).map(|e| e * 10).sum();
-            (hn, prs)
-        })
-        .inspect(|(hn, prs)| {
-            eprintln!("House: {hn}, presents: {prs}");
-        })
-        .skip_while(|(_, prs)| prs < input)
-        .map(|(hn, _)| hn)
-        .next()
-        .unwrap()
+/// The presents delivered to each house up to `last`, where elf `e` leaves `multiplier * e`
+/// presents at each multiple of `e`, stopping after `cap` houses if there is one. Counts that do
+/// not fit in a `Presents` are `Presents::MAX`.
+fn deliveries(last: usize, multiplier: Presents, cap: Option<usize>) -> Vec<Presents> {
+    let mut houses: Vec<Presents> = vec![0; last + 1];
+    for elf in 1..=last {
+        checkpoint();
+        // Counts saturate, as no target is above `Presents::MAX`
+        let presents = multiplier.saturating_mul(Presents::try_from(elf).unwrap_or(Presents::MAX));
+        let visited = (elf..=last).step_by(elf).take(cap.unwrap_or(usize::MAX));
+        for house in visited {
+            houses[house] = houses[house].saturating_add(presents);
+        }
+    }
+    houses
 }
 
-#[derive(Debug)]
-struct Elf {
-    number: u32,
-    deliveries: u32,
+/// The first house to get at least `target` presents. Elf `n` always brings `multiplier * n`
+/// presents to house `n`, which bounds the houses to look at.
+fn first_house(target: Presents, multiplier: Presents, cap: Option<usize>) -> HouseNumber {
+    let last = target.div_ceil(multiplier).max(1);
+    let houses = deliveries(last as usize, multiplier, cap);
+    let house = (1..houses.len())
+        .find(|h| houses[*h] >= target)
+        .expect("House `last` gets enough presents") as HouseNumber;
+    debug!("House {house} gets {} presents", houses[house as usize]);
+    house
 }
 
-impl Elf {
-    fn new(number: u32) -> Self {
-        Self {
-            number,
-            deliveries: 50,
-        }
-    }
-
-    fn deliver_presents(&mut self) -> u32 {
-        self.deliveries -= 1;
-        self.number * 11
-    }
+#[aoc(day20, part1)]
+fn part1(input: &Presents) -> HouseNumber {
+    first_house(*input, 10, None)
 }
 
+/// Each elf stops after 50 houses
 #[aoc(day20, part2)]
 fn part2(input: &Presents) -> HouseNumber {
-    // house numbers
-    let mut house_number = 1;
-    let mut elves = vec![];
-
-    loop {
-        elves.push(Elf::new(house_number));
-
-        // elves bearing presents
-        let mut prs = 0;
-        for elf in &mut elves {
-            if house_number % elf.number == 0 {
-                prs += elf.deliver_presents();
-            }
-        }
-
-        elves.retain(|e| e.deliveries > 0);
-
-        eprintln!("House: {house_number}, presents: {prs}");
-
-        if prs >= *input {
-            break house_number;
-        }
-
-        house_number += 1;
-    }
+    first_house(*input, 11, Some(50))
 }
 
 #[cfg(test)]
@@ -92,4 +69,27 @@ mod tests {
             assert_eq!(hn, part2(&prs));
         }
     }
+
+    #[test]
+    fn deliveries_by_trial_division() {
+        let houses = deliveries(200, 11, Some(3));
+        for (house, presents) in houses.iter().enumerate().skip(1) {
+            let elves = (1..=house).filter(|e| house % e == 0 && house / e <= 3);
+            assert_eq!(
+                *presents as usize,
+                elves.map(|e| e * 11).sum(),
+                "house {house}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_target() {
+        // Houses 4 and beyond get more presents than fit in a `Presents`
+        let multiplier = Presents::MAX / 4;
+        let houses = deliveries(6, multiplier, None);
+        assert_eq!(houses[3], 4 * multiplier);
+        assert_eq!(houses[4..], [Presents::MAX; 3]);
+        assert_eq!(first_house(Presents::MAX, multiplier, None), 4);
+    }
 }
@@ -1,337 +1,337 @@
-use std::collections::VecDeque;
-use std::num::Saturating;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::{debug, info};
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-struct Weapon {
-    name: &'static str,
-    damage: u32,
-}
+/// The shop of the puzzle, in the format of the puzzle text
+const SHOP: &str = "\
+Weapons:    Cost  Damage  Armor
+Dagger        8     4       0
+Shortsword   10     5       0
+Warhammer    25     6       0
+Longsword    40     7       0
+Greataxe     74     8       0
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Armor {
-    name: &'static str,
-    armor: u32,
-}
+Armor:      Cost  Damage  Armor
+Leather      13     0       1
+Chainmail    31     0       2
+Splintmail   53     0       3
+Bandedmail   75     0       4
+Platemail   102     0       5
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Ring {
-    name: &'static str,
-    armor: u32,
-    damage: u32,
-}
+Rings:      Cost  Damage  Armor
+Damage +1    25     1       0
+Damage +2    50     2       0
+Damage +3   100     3       0
+Defense +1   20     0       1
+Defense +2   40     0       2
+Defense +3   80     0       3
+";
 
-#[derive(Clone, Copy)]
-enum Item {
-    Weapon(Weapon),
-    Armor(Armor),
-    Ring(Ring),
+/// How many items of each category of the shop a loadout has
+const LIMITS: &[(&str, RangeInclusive<usize>)] =
+    &[("Weapons", 1..=1), ("Armor", 0..=1), ("Rings", 0..=2)];
+
+const PLAYER_HP: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Item {
+    name: String,
+    cost: u32,
+    damage: u32,
+    armor: u32,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-struct Fighter {
-    spent: u32,
-    hp: Saturating<u32>,
-    armor_: Option<Armor>,
-    weapon: Weapon,
-    ring1: Option<Ring>,
-    ring2: Option<Ring>,
+/// The items for sale, by category
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shop {
+    categories: Vec<(String, Vec<Item>)>,
 }
 
-impl Fighter {
-    fn new() -> Self {
-        Self {
-            hp: Saturating(100),
-            ..Default::default()
+impl Shop {
+    /// Parse tables of `NAME COST DAMAGE ARMOR` lines under a `CATEGORY: Cost Damage Armor` header
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        let source = Source::new(text);
+        let mut categories: Vec<(String, Vec<Item>)> = vec![];
+        for line in source.lines() {
+            if let Some((category, columns)) = line.split_once(':') {
+                let mut words = source.words(columns);
+                for column in ["Cost", "Damage", "Armor"] {
+                    words.expect(column)?;
+                }
+                categories.push((category.to_string(), vec![]));
+                continue;
+            }
+            let Some((_, items)) = categories.last_mut() else {
+                return Err(source.error(line, "a category such as [Weapons:]"));
+            };
+            // The name can have spaces, so the numbers are the last three words
+            let words: Vec<_> = line.split_ascii_whitespace().collect();
+            let [cost, damage, armor] = words[words.len().saturating_sub(3)..] else {
+                return Err(source.end_error(line, "a name, a cost, a damage and an armor"));
+            };
+            let name = line[..cost.as_ptr() as usize - line.as_ptr() as usize].trim_end();
+            if name.is_empty() {
+                return Err(source.error(line, "a name, a cost, a damage and an armor"));
+            }
+            items.push(Item {
+                name: name.to_string(),
+                cost: source.parse(cost, "a cost")?,
+                damage: source.parse(damage, "a damage")?,
+                armor: source.parse(armor, "an armor")?,
+            });
         }
+        Ok(Self { categories })
     }
 
-    fn damage(self) -> u32 {
-        let mut damage = self.weapon.damage;
-        if let Some(r1) = self.ring1 {
-            damage += r1.damage;
-        }
-        if let Some(r2) = self.ring2 {
-            damage += r2.damage;
-        }
-        damage
+    fn category(&self, name: &str) -> &[Item] {
+        self.categories
+            .iter()
+            .find(|(category, _)| category == name)
+            .map_or(&[], |(_, items)| items)
     }
 
-    fn armor(self) -> u32 {
-        let mut armor = 0;
-        if let Some(a) = self.armor_ {
-            armor += a.armor;
+    /// Every loadout of distinct items within the limits
+    fn loadouts(&self, limits: &[(&str, RangeInclusive<usize>)]) -> Vec<Loadout<'_>> {
+        let mut loadouts = vec![Loadout::default()];
+        for (category, range) in limits {
+            let choices = combinations(self.category(category), range.clone());
+            loadouts = loadouts
+                .iter()
+                .flat_map(|loadout| {
+                    choices.iter().map(|choice| Loadout {
+                        items: [loadout.items.as_slice(), choice].concat(),
+                    })
+                })
+                .collect();
         }
-        if let Some(r1) = self.ring1 {
-            armor += r1.armor;
-        }
-        if let Some(r2) = self.ring2 {
-            armor += r2.armor;
-        }
-        armor
+        loadouts
     }
+}
 
-    fn buy(mut self, price: u32, item: Item) -> Option<Self> {
-        self.spent += price;
-        match item {
-            Item::Weapon(w) if self.weapon.damage == 0 => {
-                self.weapon = w;
-                Some(self)
-            }
-            Item::Armor(a) if self.armor_.is_none() => {
-                self.armor_ = Some(a);
-                Some(self)
-            }
-            Item::Ring(r) => {
-                if self.ring1.is_none() {
-                    self.ring1 = Some(r);
-                    Some(self)
-                } else if self.ring2.is_none() && self.ring1.unwrap() != r {
-                    self.ring2 = Some(r);
-                    Some(self)
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
+/// The ways to pick a number of distinct items within `sizes`
+fn combinations(items: &[Item], sizes: RangeInclusive<usize>) -> Vec<Vec<&Item>> {
+    let mut picks: Vec<Vec<&Item>> = vec![vec![]];
+    for item in items {
+        let with_item: Vec<_> = picks
+            .iter()
+            .filter(|pick| pick.len() < *sizes.end())
+            .map(|pick| [pick.as_slice(), &[item]].concat())
+            .collect();
+        picks.extend(with_item);
     }
+    picks.retain(|pick| sizes.contains(&pick.len()));
+    picks
+}
 
-    fn fight(mut self, mut other: Self) -> bool {
-        loop {
-            self.attack(&mut other);
-            if other.hp == Saturating(0) {
-                break true;
-            }
-            other.attack(&mut self);
-            if self.hp == Saturating(0) {
-                break false;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Stats {
+    pub(crate) hp: u32,
+    pub(crate) damage: u32,
+    pub(crate) armor: u32,
+}
+
+impl Stats {
+    /// Parse the `Hit Points`, `Damage` and `Armor` of the boss, of which only the armor can be
+    /// left out
+    pub(crate) fn parse(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let (mut hp, mut damage, mut armor) = (None, None, 0);
+        for line in source.lines() {
+            let (field, amount) = source.split_once(line, ": ")?;
+            let amount = source.parse(amount, "an amount")?;
+            match field {
+                "Hit Points" => hp = Some(amount),
+                "Damage" => damage = Some(amount),
+                "Armor" => armor = amount,
+                _ => return Err(source.error(field, "[Hit Points], [Damage] or [Armor]")),
             }
         }
+        Ok(Self {
+            hp: hp.ok_or_else(|| source.end_error(input, "[Hit Points]"))?,
+            damage: damage.ok_or_else(|| source.end_error(input, "[Damage]"))?,
+            armor,
+        })
     }
 
-    fn attack(self, other: &mut Self) {
-        dbg!(self.damage(), other.armor());
-        other.hp -= 1.max(self.damage().saturating_sub(other.armor()));
-        dbg!(other.hp);
+    /// The damage of an attack on `other`, which is at least 1
+    pub(crate) fn damage_to(&self, other: &Self) -> u32 {
+        self.damage.saturating_sub(other.armor).max(1)
     }
-}
 
-const STORE: [(u32, Item); 16] = [
-    // Weapons:
-    (
-        8,
-        Item::Weapon(Weapon {
-            name: "Dagger",
-            damage: 4,
-        }),
-    ),
-    (
-        10,
-        Item::Weapon(Weapon {
-            name: "Shortsword",
-            damage: 5,
-        }),
-    ),
-    (
-        25,
-        Item::Weapon(Weapon {
-            name: "Warhammer",
-            damage: 6,
-        }),
-    ),
-    (
-        40,
-        Item::Weapon(Weapon {
-            name: "Longsword",
-            damage: 7,
-        }),
-    ),
-    (
-        74,
-        Item::Weapon(Weapon {
-            name: "Greataxe",
-            damage: 8,
-        }),
-    ),
-    // Armor:
-    (
-        13,
-        Item::Armor(Armor {
-            name: "Leather",
-            armor: 1,
-        }),
-    ),
-    (
-        31,
-        Item::Armor(Armor {
-            name: "Chainmail",
-            armor: 2,
-        }),
-    ),
-    (
-        53,
-        Item::Armor(Armor {
-            name: "Splintmail",
-            armor: 3,
-        }),
-    ),
-    (
-        75,
-        Item::Armor(Armor {
-            name: "Bandedmail",
-            armor: 4,
-        }),
-    ),
-    (
-        102,
-        Item::Armor(Armor {
-            name: "Platemail",
-            armor: 5,
-        }),
-    ),
-    // Rings:
-    (
-        25,
-        Item::Ring(Ring {
-            name: "Damage +1",
-            damage: 1,
-            armor: 0,
-        }),
-    ),
-    (
-        50,
-        Item::Ring(Ring {
-            name: "Damage +2",
-            damage: 2,
-            armor: 0,
-        }),
-    ),
-    (
-        100,
-        Item::Ring(Ring {
-            name: "Damage +3",
-            damage: 3,
-            armor: 0,
-        }),
-    ),
-    (
-        20,
-        Item::Ring(Ring {
-            name: "Defense +1",
-            damage: 0,
-            armor: 1,
-        }),
-    ),
-    (
-        40,
-        Item::Ring(Ring {
-            name: "Defense +2",
-            damage: 0,
-            armor: 2,
-        }),
-    ),
-    (
-        80,
-        Item::Ring(Ring {
-            name: "Defense +3",
-            damage: 0,
-            armor: 3,
-        }),
-    ),
-];
+    /// The attacks it takes to bring `other` down
+    fn attacks_to_kill(&self, other: &Self) -> u32 {
+        other.hp.div_ceil(self.damage_to(other))
+    }
 
-#[aoc_generator(day21)]
-fn parse(input: &str) -> Fighter {
-    let mut fighter = Fighter::new();
-    for line in input.lines() {
-        let (field, amt) = line.trim().split_once(": ").unwrap();
-        let amount = amt.parse().unwrap();
-        match field {
-            "Hit Points" => {
-                fighter.hp = Saturating(amount);
-            }
-            "Damage" => {
-                fighter.weapon = Weapon {
-                    name: "",
-                    damage: amount,
-                };
-            }
-            "Armor" => {
-                fighter.armor_ = Some(Armor {
-                    name: "",
-                    armor: amount,
-                });
-            }
-            _ => {}
-        }
+    /// Whether this fighter wins, attacking first
+    fn beats(&self, other: &Self) -> bool {
+        self.attacks_to_kill(other) <= other.attacks_to_kill(self)
     }
-    fighter
 }
 
-#[aoc(day21, part1)]
-fn part1(boss: &Fighter) -> u32 {
-    let mut fighters = vec![];
-    let mut queue = VecDeque::from([Fighter::new()]);
-
-    while let Some(fighter) = queue.pop_front() {
-        dbg!(fighter);
-        if fighters.contains(&fighter) {
-            continue;
-        }
-        if fighter.fight(*boss) {
-            fighters.push(fighter);
-        }
-        for (price, item) in STORE {
-            if let Some(f) = fighter.buy(price, item) {
-                queue.push_back(f);
-            }
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Loadout<'a> {
+    items: Vec<&'a Item>,
+}
+
+impl Loadout<'_> {
+    fn cost(&self) -> u32 {
+        self.items.iter().map(|i| i.cost).sum()
+    }
+
+    fn stats(&self, hp: u32) -> Stats {
+        Stats {
+            hp,
+            damage: self.items.iter().map(|i| i.damage).sum(),
+            armor: self.items.iter().map(|i| i.armor).sum(),
         }
     }
+}
 
-    fighters.iter().min_by_key(|f| f.spent).unwrap().spent
+impl Display for Loadout<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self.items.iter().map(|i| i.name.as_str()).collect();
+        write!(f, "{} for {}", names.join(", "), self.cost())
+    }
 }
 
-#[aoc(day21, part2)]
-fn part2(boss: &Fighter) -> u32 {
-    let mut fighters = vec![];
-    let mut queue = VecDeque::from([Fighter::new()]);
+struct Game {
+    shop: Shop,
+    boss: Stats,
+}
 
-    while let Some(fighter) = queue.pop_front() {
-        if fighters.contains(&fighter) {
-            continue;
-        }
-        dbg!(fighter);
-        if fighter.weapon.damage != 0 && !fighter.fight(*boss) {
-            fighters.push(fighter);
-        }
-        for (price, item) in STORE {
-            if let Some(f) = fighter.buy(price, item) {
-                queue.push_back(f);
-            }
-        }
+impl Game {
+    /// The loadouts with which the player wins or loses, from the cheapest
+    fn outcomes(&self, wins: bool) -> Vec<Loadout<'_>> {
+        let mut loadouts: Vec<_> = self
+            .shop
+            .loadouts(LIMITS)
+            .into_iter()
+            .filter(|l| l.stats(PLAYER_HP).beats(&self.boss) == wins)
+            .collect();
+        loadouts.sort_by_key(Loadout::cost);
+        debug!(
+            "{} loadouts that {}",
+            loadouts.len(),
+            if wins { "win" } else { "lose" }
+        );
+        loadouts
     }
+}
 
-    fighters.iter().max_by_key(|f| f.spent).unwrap().spent
+#[aoc_generator(day21)]
+fn parse(input: &str) -> Result<Game, ParseError> {
+    aoc_common::logging::init();
+    Ok(Game {
+        shop: Shop::parse(SHOP)?,
+        boss: Stats::parse(input)?,
+    })
+}
+
+/// The cheapest loadout that wins
+#[aoc(day21, part1)]
+fn part1(game: &Game) -> u32 {
+    let wins = game.outcomes(true);
+    let cheapest = wins.first().expect("Some loadout wins");
+    info!("Cheapest win: {cheapest}");
+    cheapest.cost()
+}
+
+/// The priciest loadout that loses
+#[aoc(day21, part2)]
+fn part2(game: &Game) -> u32 {
+    let losses = game.outcomes(false);
+    let priciest = losses.last().expect("Some loadout loses");
+    info!("Priciest loss: {priciest}");
+    priciest.cost()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// The fight, one attack at a time
+    fn simulate(mut player: Stats, mut boss: Stats) -> bool {
+        loop {
+            boss.hp = boss.hp.saturating_sub(player.damage_to(&boss));
+            if boss.hp == 0 {
+                return true;
+            }
+            player.hp = player.hp.saturating_sub(boss.damage_to(&player));
+            if player.hp == 0 {
+                return false;
+            }
+        }
+    }
+
     #[test]
     fn test_fight() {
-        let player = parse(
-            "Hit Points: 8
-            Damage: 5
-            Armor: 5",
+        let player = Stats {
+            hp: 8,
+            damage: 5,
+            armor: 5,
+        };
+        let boss = Stats {
+            hp: 12,
+            damage: 7,
+            armor: 2,
+        };
+        assert_eq!(player.attacks_to_kill(&boss), 4);
+        assert_eq!(boss.attacks_to_kill(&player), 4);
+        assert!(player.beats(&boss));
+
+        assert_eq!(
+            Stats::parse("Hit Points: 12\nDamage: 7\nArmor: 2"),
+            Ok(boss)
+        );
+        assert_eq!(
+            Stats::parse("Hit Points: 12\nArmor: 2")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 9: expected [Damage], found end of line"
         );
-        let boss = parse(
-            "Hit Points: 12
-            Damage: 7
-            Armor: 2",
+
+        for damage in 0..10 {
+            for armor in 0..10 {
+                let player = Stats {
+                    hp: 20,
+                    damage,
+                    armor,
+                };
+                assert_eq!(player.beats(&boss), simulate(player, boss), "{player:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_shop() {
+        let shop = Shop::parse(SHOP).unwrap();
+        let sizes: Vec<_> = shop
+            .categories
+            .iter()
+            .map(|(name, items)| (name.as_str(), items.len()))
+            .collect();
+        assert_eq!(sizes, [("Weapons", 5), ("Armor", 5), ("Rings", 6)]);
+        assert_eq!(
+            shop.category("Rings")[3],
+            Item {
+                name: String::from("Defense +1"),
+                cost: 20,
+                damage: 0,
+                armor: 1,
+            }
+        );
+
+        // A weapon, no armor or one of 5, and up to two of 6 rings
+        assert_eq!(shop.loadouts(LIMITS).len(), 5 * 6 * (1 + 6 + 15));
+
+        let err = Shop::parse("Weapons: Cost Damage Armor\nDagger 8 x 0").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 10: expected a damage, found [x]"
         );
-        assert!(player.fight(boss));
     }
 }
@@ -0,0 +1,271 @@
+use aoc_common::parse::ParseError;
+use aoc_common::search::dijkstra;
+use aoc_runner_derive::{aoc, aoc_generator};
+use log::info;
+
+use crate::day21::Stats;
+
+/// What a spell does over the turns it lasts, starting with the turn after it is cast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Effect {
+    turns: u8,
+    armor: u32,
+    damage: u32,
+    mana: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Spell {
+    name: &'static str,
+    cost: u32,
+    damage: u32,
+    heal: u32,
+    effect: Option<Effect>,
+}
+
+const SPELLS: [Spell; 5] = [
+    Spell {
+        name: "Magic Missile",
+        cost: 53,
+        damage: 4,
+        heal: 0,
+        effect: None,
+    },
+    Spell {
+        name: "Drain",
+        cost: 73,
+        damage: 2,
+        heal: 2,
+        effect: None,
+    },
+    Spell {
+        name: "Shield",
+        cost: 113,
+        damage: 0,
+        heal: 0,
+        effect: Some(Effect {
+            turns: 6,
+            armor: 7,
+            damage: 0,
+            mana: 0,
+        }),
+    },
+    Spell {
+        name: "Poison",
+        cost: 173,
+        damage: 0,
+        heal: 0,
+        effect: Some(Effect {
+            turns: 6,
+            armor: 0,
+            damage: 3,
+            mana: 0,
+        }),
+    },
+    Spell {
+        name: "Recharge",
+        cost: 229,
+        damage: 0,
+        heal: 0,
+        effect: Some(Effect {
+            turns: 5,
+            armor: 0,
+            damage: 0,
+            mana: 101,
+        }),
+    },
+];
+
+/// The battle at the point where the player picks a spell, after the effects of the turn
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Battle {
+    hp: u32,
+    mana: u32,
+    boss_hp: u32,
+    /// The turns left to each effect of `SPELLS`
+    timers: [u8; SPELLS.len()],
+}
+
+impl Battle {
+    /// Apply the active effects at the start of a turn, returning the armor they give the player
+    fn tick(&mut self) -> u32 {
+        let mut armor = 0;
+        for (spell, timer) in SPELLS.iter().zip(&mut self.timers) {
+            if let Some(effect) = spell.effect.filter(|_| *timer > 0) {
+                self.boss_hp = self.boss_hp.saturating_sub(effect.damage);
+                self.mana += effect.mana;
+                armor += effect.armor;
+                *timer -= 1;
+            }
+        }
+        armor
+    }
+
+    /// The start of the player's turn, or `None` when the player does not live to see it
+    fn player_turn(mut self, hard: bool) -> Option<Self> {
+        if hard {
+            self.hp = self.hp.checked_sub(1).filter(|hp| *hp > 0)?;
+        }
+        self.tick();
+        Some(self)
+    }
+
+    /// Cast a spell, take the boss's attack, and start the next turn of the player, or `None` if
+    /// the spell cannot be cast or the player dies
+    fn cast(&self, i: usize, boss: &Stats, hard: bool) -> Option<Self> {
+        let spell = &SPELLS[i];
+        if self.mana < spell.cost || self.timers[i] > 0 {
+            return None;
+        }
+        let mut next = self.clone();
+        next.mana -= spell.cost;
+        next.boss_hp = next.boss_hp.saturating_sub(spell.damage);
+        next.hp += spell.heal;
+        if let Some(effect) = spell.effect {
+            next.timers[i] = effect.turns;
+        }
+        if next.boss_hp == 0 {
+            return Some(next);
+        }
+
+        let armor = next.tick();
+        if next.boss_hp == 0 {
+            return Some(next);
+        }
+        let player = Stats {
+            hp: next.hp,
+            damage: 0,
+            armor,
+        };
+        next.hp = next
+            .hp
+            .checked_sub(boss.damage_to(&player))
+            .filter(|hp| *hp > 0)?;
+        next.player_turn(hard)
+    }
+}
+
+/// The cheapest spells to cast to win, and the mana they cost. In hard mode, the player loses 1 hit
+/// point at the start of each of their turns.
+fn cheapest_win(
+    player: Stats,
+    mana: u32,
+    boss: &Stats,
+    hard: bool,
+) -> Option<(u32, Vec<&'static str>)> {
+    let start = Battle {
+        hp: player.hp,
+        mana,
+        boss_hp: boss.hp,
+        timers: [0; SPELLS.len()],
+    }
+    .player_turn(hard)?;
+    let (cost, path) = dijkstra(
+        start,
+        |battle| {
+            let battle = battle.clone();
+            (0..SPELLS.len()).filter_map(move |i| {
+                let next = battle.cast(i, boss, hard)?;
+                Some((next, u64::from(SPELLS[i].cost)))
+            })
+        },
+        |battle| battle.boss_hp == 0,
+    )?;
+    // The battles do not keep the spells cast, so that battles reached in different ways are the
+    // same state, and each step of the path is the one spell that leads from a battle to the next
+    let spells = path
+        .windows(2)
+        .map(|pair| {
+            let i = (0..SPELLS.len())
+                .find(|i| pair[0].cast(*i, boss, hard).as_ref() == Some(&pair[1]))
+                .expect("A spell leads to the next battle");
+            SPELLS[i].name
+        })
+        .collect();
+    Some((cost as u32, spells))
+}
+
+const PLAYER: Stats = Stats {
+    hp: 50,
+    damage: 0,
+    armor: 0,
+};
+
+const PLAYER_MANA: u32 = 500;
+
+#[aoc_generator(day22)]
+fn parse(input: &str) -> Result<Stats, ParseError> {
+    aoc_common::logging::init();
+    Stats::parse(input)
+}
+
+fn solve(boss: &Stats, hard: bool) -> u32 {
+    let (cost, spells) = cheapest_win(PLAYER, PLAYER_MANA, boss, hard).expect("The player can win");
+    info!("Cheapest win: {}", spells.join(", "));
+    cost
+}
+
+#[aoc(day22, part1)]
+fn part1(boss: &Stats) -> u32 {
+    solve(boss, false)
+}
+
+/// The player loses 1 hit point at the start of each of their turns
+#[aoc(day22, part2)]
+fn part2(boss: &Stats) -> u32 {
+    solve(boss, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: Stats = Stats {
+        hp: 10,
+        damage: 0,
+        armor: 0,
+    };
+
+    #[test]
+    fn part1_example() {
+        let boss = parse("Hit Points: 13\nDamage: 8").unwrap();
+        assert_eq!(
+            cheapest_win(PLAYER, 250, &boss, false),
+            Some((173 + 53, vec!["Poison", "Magic Missile"]))
+        );
+    }
+
+    #[test]
+    fn part1_example_with_every_spell() {
+        let boss = parse("Hit Points: 14\nDamage: 8").unwrap();
+        let (cost, spells) = cheapest_win(PLAYER, 250, &boss, false).unwrap();
+        assert_eq!(
+            spells,
+            ["Recharge", "Shield", "Drain", "Poison", "Magic Missile"]
+        );
+        assert_eq!(cost, 229 + 113 + 73 + 173 + 53);
+    }
+
+    #[test]
+    fn part2_example() {
+        // Losing a hit point each turn, the player dies before the poison can finish the boss
+        let boss = parse("Hit Points: 13\nDamage: 8").unwrap();
+        assert_eq!(cheapest_win(PLAYER, 250, &boss, true), None);
+    }
+
+    #[test]
+    fn test_full_fight() {
+        let boss = parse("Hit Points: 51\nDamage: 9").unwrap();
+        assert_eq!(part1(&boss), 900);
+        assert_eq!(part2(&boss), 1216);
+    }
+
+    #[test]
+    fn test_truncated_boss() {
+        let err = parse("Damage: 8").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 10: expected [Hit Points], found end of line"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod day22;
 mod day21;
 mod day20;
 mod day19;
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_common::parse::{ParseError, Source, Words};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 type BotId = u32;
 type OutputId = u32;
@@ -13,13 +15,15 @@ enum Dest {
 }
 
 impl Dest {
-    fn new(kind: &str, id: &str) -> Self {
-        let id = id.parse().unwrap();
-        if kind == "output" {
+    /// Parse a destination such as `output 3`
+    fn parse(words: &mut Words) -> Result<Self, ParseError> {
+        let kind = words.one_of(&["bot", "output"])?;
+        let id = words.parse("an id")?;
+        Ok(if kind == "output" {
             Self::Output(id)
         } else {
             Self::Bot(id)
-        }
+        })
     }
 
     fn send(self, chip: Chip, factory: &mut Factory) -> Option<BotAction> {
@@ -66,32 +70,27 @@ enum Instruction {
 type Input = Vec<Instruction>;
 
 #[aoc_generator(day10)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let mut words = l.trim().split_ascii_whitespace();
-            match words.next().unwrap() {
-                "value" => {
-                    let value = words.next().unwrap().parse().unwrap();
-                    let bot = words.nth(3).unwrap().parse().unwrap();
-                    Instruction::ToBot(ToBot { value, bot })
-                }
-                "bot" => {
-                    let bot = words.next().unwrap().parse().unwrap();
-                    let low = {
-                        let kind = words.nth(3).unwrap();
-                        let id = words.next().unwrap();
-                        Dest::new(kind, id)
-                    };
-                    let high = {
-                        let kind = words.nth(3).unwrap();
-                        let id = words.next().unwrap();
-                        Dest::new(kind, id)
-                    };
-                    Instruction::FromBot(FromBot { bot, low, high })
-                }
-                _ => unimplemented!(),
+            let mut words = source.words(l);
+            if words.one_of(&["value", "bot"])? == "value" {
+                // value 5 goes to bot 2
+                let value = words.parse("a value")?;
+                words.skip(3)?;
+                let bot = words.parse("a bot id")?;
+                Ok(Instruction::ToBot(ToBot { value, bot }))
+            } else {
+                // bot 2 gives low to bot 1 and high to output 0
+                let bot = words.parse("a bot id")?;
+                words.skip(3)?;
+                let low = Dest::parse(&mut words)?;
+                words.skip(3)?;
+                let high = Dest::parse(&mut words)?;
+                Ok(Instruction::FromBot(FromBot { bot, low, high }))
             }
         })
         .collect()
@@ -128,10 +127,9 @@ impl Bot {
     }
 
     fn receive(&mut self, chip: Chip) -> Option<BotAction> {
-        print!("Bot {} receives {chip}", self.id);
         self.chips.push(chip);
         if self.chips.len() < 2 {
-            println!();
+            debug!("Bot {} receives {chip}", self.id);
             None
         } else {
             let high = self.chips.iter().copied().max().unwrap();
@@ -139,8 +137,9 @@ impl Bot {
 
             self.chips.clear();
 
-            println!(
-                ", and passes low value {low} to {}, high value {high} to {}",
+            debug!(
+                "Bot {} receives {chip}, and passes low value {low} to {}, high value {high} to {}",
+                self.id,
                 self.instr.low.display(),
                 self.instr.high.display()
             );
@@ -257,14 +256,17 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        let mut factory = Factory::new(&parse(
-            "value 5 goes to bot 2
+        let mut factory = Factory::new(
+            &parse(
+                "value 5 goes to bot 2
              bot 2 gives low to bot 1 and high to bot 0
              value 3 goes to bot 1
              bot 1 gives low to output 1 and high to bot 0
              bot 0 gives low to output 2 and high to output 0
              value 2 goes to bot 2",
-        ));
+            )
+            .unwrap(),
+        );
         factory.run();
 
         assert_eq!(
@@ -1,156 +1,194 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashSet;
 
-#[derive(Debug, Clone, Copy)]
-enum Dir {
-    U,
-    D,
-    R,
-    L,
-}
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::search::bfs;
+use aoc_common::{Direction, Grid};
+use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
-type Dirs = Vec<Dir>;
+type Dirs = Vec<Direction>;
 type Lines = Vec<Dirs>;
 type Key = char;
 
-struct Keypad {
-    ln_len: usize,
-    vec: Vec<Key>,
+/// The text of a keypad, and the key the finger starts on
+struct Layout {
+    keys: &'static str,
+    start: Key,
 }
 
-impl Keypad {
-    fn new(s: &str, ln_len: usize) -> Self {
-        let vec = s
-            .lines()
-            .filter(|l| !l.is_empty())
-            .flat_map(|l| l.chars())
-            .collect();
-
-        Self { ln_len, vec }
-    }
+const SQUARE: Layout = Layout {
+    keys: "
+123
+456
+789
+",
+    start: '5',
+};
 
-    /// Find the index of a key character
-    fn index_of(&self, key: Key) -> usize {
-        self.vec.iter().position(|k| k == &key).unwrap()
-    }
+const DIAMOND: Layout = Layout {
+    keys: "
+  1
+ 234
+56789
+ ABC
+  D
+",
+    start: '5',
+};
+
+/// A keypad, with a finger on one of its keys
+struct Keypad {
+    /// The keys, with `None` for the cells without one
+    keys: Grid<Option<Key>>,
+    start: usize,
+}
 
-    /// Move from the specified position in the specified direction.
-    /// Returns the value of the new position and its index
-    fn move_from(&self, pos: usize, dir: Dir) -> (Key, usize) {
-        let new_pos = {
-            match dir {
-                Dir::U => {
-                    if pos < self.ln_len {
-                        pos
-                    } else {
-                        pos - self.ln_len
-                    }
-                }
-                Dir::D => {
-                    if pos >= self.vec.len() - self.ln_len {
-                        pos
-                    } else {
-                        pos + self.ln_len
-                    }
-                }
-                Dir::R => {
-                    if pos % self.ln_len == self.ln_len - 1 {
-                        pos
-                    } else {
-                        pos + 1
-                    }
-                }
-                Dir::L => {
-                    if pos % self.ln_len == 0 {
-                        pos
-                    } else {
-                        pos - 1
-                    }
-                }
+impl Keypad {
+    /// A keypad laid out one row per line, with spaces or dots for the cells without a key, starting
+    /// on the key `start`. Rows shorter than the longest are padded with cells without a key.
+    fn new(layout: &str, start: Key) -> Result<Self, ParseError> {
+        let source = Source::new(layout);
+        let rows: Vec<_> = layout.lines().filter(|l| !l.trim().is_empty()).collect();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        let mut keys = Vec::with_capacity(width * rows.len());
+        let mut seen = HashSet::new();
+        for row in &rows {
+            for (i, c) in row.char_indices() {
+                let key = match c {
+                    ' ' | '.' => None,
+                    c if seen.insert(c) => Some(c),
+                    _ => return Err(source.error(&row[i..i + c.len_utf8()], "a key seen once")),
+                };
+                keys.push(key);
             }
-        };
-        let key = self.vec[new_pos];
-        if key == ' ' {
-            return (self.vec[pos], pos);
+            keys.resize(keys.len() + width - row.chars().count(), None);
         }
-
-        (self.vec[new_pos], new_pos)
+        if keys.is_empty() {
+            return Err(source.end_error(layout, "a key"));
+        }
+        let keys = Grid::new(width, keys);
+        let start = keys
+            .iter()
+            .position(|k| *k == Some(start))
+            .ok_or_else(|| source.end_error(layout, format!("the start key [{start}]")))?;
+        Ok(Self { keys, start })
     }
 
-    /// Follow a set of directions to arrive at a final key
-    fn follow_dirs(&self, dirs: &[Dir], init_key: Key) -> Key {
-        let mut key = init_key;
-        let mut pos = self.index_of(key);
+    fn key(&self, index: usize) -> Key {
+        self.keys[index].expect("The finger is on a key")
+    }
 
-        for dir in dirs {
-            (key, pos) = self.move_from(pos, *dir);
-        }
+    fn index_of(&self, key: Key) -> Option<usize> {
+        self.keys.iter().position(|k| *k == Some(key))
+    }
 
-        key
+    /// Move a finger, which stays where it is rather than leave the keys
+    fn step(&self, index: usize, dir: Direction) -> usize {
+        let (x, y) = self.keys.coords(index);
+        let (dx, dy) = dir.offset();
+        x.checked_add_signed(dx as isize)
+            .zip(y.checked_add_signed(-dy as isize))
+            .and_then(|(x, y)| self.keys.index_of(x, y))
+            .filter(|i| self.keys[*i].is_some())
+            .unwrap_or(index)
     }
 
-    /// Follow multiple sets of directions to get a multidigit code
-    fn follow_dir_lists(&self, dir_lists: &[Dirs]) -> String {
-        let initial_key = '5';
-        dir_lists
+    /// The code pressed following a line of directions for each key
+    fn code(&self, lines: &[Dirs]) -> String {
+        let mut index = self.start;
+        lines
             .iter()
-            .fold(vec![], |mut digits, list| {
-                let key = digits.last().copied().unwrap_or(initial_key);
-                let new_key = self.follow_dirs(list, key);
-                digits.push(new_key);
-                digits
+            .map(|line| {
+                index = line.iter().fold(index, |i, dir| self.step(i, *dir));
+                self.key(index)
+            })
+            .collect()
+    }
+
+    /// The shortest lines of directions for a code, each with at least one direction as a line
+    /// cannot be empty, or `None` if a key of the code cannot be reached
+    fn instructions(&self, code: &str) -> Option<Lines> {
+        let mut index = self.start;
+        code.chars()
+            .map(|key| {
+                let target = self.index_of(key)?;
+                // With the direction that led to each position, so that the line is not empty
+                let path = bfs(
+                    (index, None),
+                    |(i, _)| Direction::ALL.map(|dir| (self.step(*i, dir), Some(dir))),
+                    |(i, dir)| *i == target && dir.is_some(),
+                )?;
+                index = target;
+                Some(path.iter().filter_map(|(_, dir)| *dir).collect())
             })
-            .iter()
             .collect()
     }
 }
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Lines {
-    input
+fn parse(input: &str) -> Result<Lines, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            l.trim()
-                .chars()
-                .filter_map(|c| match c {
-                    'U' => Some(Dir::U),
-                    'D' => Some(Dir::D),
-                    'R' => Some(Dir::R),
-                    'L' => Some(Dir::L),
-                    _ => None,
+            l.char_indices()
+                .map(|(i, c)| {
+                    Direction::from_char(c).ok_or_else(|| {
+                        source.error(&l[i..i + c.len_utf8()], "[U], [D], [L] or [R]")
+                    })
                 })
                 .collect()
         })
         .collect()
 }
 
+/// Lines of directions as they are written in the input
+fn show(lines: &[Dirs]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|l| l.iter().map(|d| d.to_char()).collect())
+        .collect()
+}
+
+impl Layout {
+    fn keypad(&self) -> Keypad {
+        Keypad::new(self.keys, self.start).expect("The layout is valid")
+    }
+}
+
+/// Type the code with the shortest instructions for it, which types the same code if they are right
+fn retype(layout: &Layout, input: &Lines) -> String {
+    let keypad = layout.keypad();
+    let code = keypad.code(input);
+    let shortest = keypad
+        .instructions(&code)
+        .expect("The keys of the code are on the keypad");
+    debug!(
+        "The shortest instructions for {code}: {:?}",
+        show(&shortest)
+    );
+    keypad.code(&shortest)
+}
+
 #[aoc(day2, part1)]
 fn part1(input: &Lines) -> String {
-    let keypad = Keypad::new(
-        "
-123
-456
-789
-",
-        3,
-    );
+    SQUARE.keypad().code(input)
+}
 
-    keypad.follow_dir_lists(input)
+#[aoc(day2, part1, shortest)]
+fn part1_shortest(input: &Lines) -> String {
+    retype(&SQUARE, input)
 }
 
 #[aoc(day2, part2)]
 fn part2(input: &Lines) -> String {
-    let keypad = Keypad::new(
-        "
-  1  
- 234 
-56789
- ABC 
-  D  
-",
-        5,
-    );
+    DIAMOND.keypad().code(input)
+}
 
-    keypad.follow_dir_lists(input)
+#[aoc(day2, part2, shortest)]
+fn part2_shortest(input: &Lines) -> String {
+    retype(&DIAMOND, input)
 }
 
 #[cfg(test)]
@@ -159,27 +197,67 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(
-            part1(&parse(
-                "ULL
+        for f in [part1, part1_shortest] {
+            assert_eq!(
+                f(&parse(
+                    "ULL
                  RRDDD
                  LURDL
                  UUUUD"
-            )),
-            "1985"
-        );
+                )
+                .unwrap()),
+                "1985"
+            );
+        }
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(
-            part2(&parse(
-                "ULL
+        for f in [part2, part2_shortest] {
+            assert_eq!(
+                f(&parse(
+                    "ULL
                  RRDDD
                  LURDL
                  UUUUD"
-            )),
-            "5DB3"
+                )
+                .unwrap()),
+                "5DB3"
+            );
+        }
+    }
+
+    #[test]
+    fn test_instructions() {
+        for (layout, code) in [(SQUARE, "1985"), (DIAMOND, "5DB3"), (DIAMOND, "1D1D")] {
+            let keypad = layout.keypad();
+            let lines = keypad.instructions(code).unwrap();
+            assert_eq!(keypad.code(&lines), code);
+        }
+
+        let keypad = SQUARE.keypad();
+        let lines = keypad.instructions("51192").unwrap();
+        // Pressing the same key again takes a move out and back, or one against an edge
+        assert_eq!(
+            show(&lines).iter().map(String::len).collect::<Vec<_>>(),
+            [2, 2, 1, 4, 3]
+        );
+        assert_eq!(keypad.instructions("5X"), None);
+    }
+
+    #[test]
+    fn test_layout() {
+        let keypad = Keypad::new("A.B\nCDE", 'B').unwrap();
+        let lines = parse("L\nD\nLU").unwrap();
+        // The blank cell is walked around rather than through
+        assert_eq!(keypad.code(&lines), "BED");
+        assert_eq!(show(&keypad.instructions("A").unwrap()), ["DLLU"]);
+
+        let err = Keypad::new("12\n21", '1').err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a key seen once, found [2]"
         );
+        assert!(Keypad::new(SQUARE.keys, 'A').is_err());
     }
 }
@@ -1,18 +1,20 @@
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Spec = (u32, u32, u32);
 type Input = Vec<Spec>;
 
 #[aoc_generator(day3)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let mut specs = l.split_whitespace();
-            let h = specs.next().unwrap().parse().unwrap();
-            let w = specs.next().unwrap().parse().unwrap();
-            let l = specs.next().unwrap().parse().unwrap();
-            (h, w, l)
+            let mut specs = source.words(l);
+            let h = specs.parse("a side")?;
+            let w = specs.parse("a side")?;
+            let l = specs.parse("a side")?;
+            Ok((h, w, l))
         })
         .collect()
 }
@@ -49,20 +51,23 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse("5 10 25")), 0);
+        assert_eq!(part1(&parse("5 10 25").unwrap()), 0);
     }
 
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "101 301 501
+            part2(
+                &parse(
+                    "101 301 501
                  102 302 502
                  103 303 503
                  201 401 601
                  202 402 602
                  203 403 603"
-            )),
+                )
+                .unwrap()
+            ),
             6
         );
     }
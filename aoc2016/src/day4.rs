@@ -1,31 +1,61 @@
+use std::cmp::Reverse;
+
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::{debug, info};
+
+/// The name of the room that the North Pole objects are stored in, once decrypted
+const STORAGE: &str = "northpole object storage";
 
+/// The number of letters in a checksum
+const CHECKSUM_LEN: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Room {
     name: String,
     sector: u32,
-    checksum: Vec<char>,
+    checksum: String,
+}
+
+/// Rotate a lowercase letter forward through the alphabet
+fn rotate(c: char, by: u32) -> char {
+    let offset: u32 = b'a'.into();
+    char::from_u32(((c as u32) - offset + by % 26) % 26 + offset).unwrap()
+}
+
+/// Encrypt a decrypted name, or `None` if it has characters other than lowercase letters and
+/// spaces, which no encrypted name can decrypt to
+fn encrypt_name(name: &str, sector: u32) -> Option<String> {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' => Some(rotate(c, 26 - sector % 26)),
+            ' ' => Some('-'),
+            _ => None,
+        })
+        .collect()
 }
 
 impl Room {
-    fn is_valid(&self) -> bool {
-        let mut chars = self.name.chars().filter(char::is_ascii_alphabetic).fold(
-            Vec::new(),
-            |mut map, char| {
-                if let Some((_, v)) = map.iter_mut().find(|(c, _)| *c == char) {
-                    *v += 1;
-                } else {
-                    map.push((char, 1));
-                }
-                map
-            },
-        );
+    /// The five most common letters of the encrypted name, ties broken alphabetically
+    fn checksum_of(name: &str) -> String {
+        let mut counts = [0; 26];
+        for c in name.chars().filter(char::is_ascii_lowercase) {
+            counts[(c as u8 - b'a') as usize] += 1;
+        }
 
-        chars.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut letters: Vec<_> = (b'a'..=b'z')
+            .filter(|c| counts[(c - b'a') as usize] > 0)
+            .collect();
+        letters.sort_by_key(|c| Reverse(counts[(c - b'a') as usize]));
+        letters
+            .into_iter()
+            .take(CHECKSUM_LEN)
+            .map(char::from)
+            .collect()
+    }
 
-        self.checksum
-            .iter()
-            .zip(chars.iter().map(|c| c.0))
-            .all(|(c1, c2)| *c1 == c2)
+    fn is_valid(&self) -> bool {
+        self.checksum == Self::checksum_of(&self.name)
     }
 
     fn decrypt_name(&self) -> String {
@@ -33,8 +63,7 @@ impl Room {
             .chars()
             .map(|c| {
                 if c.is_ascii_lowercase() {
-                    let offset: u32 = b'a'.into();
-                    char::from_u32(((c as u32) - offset + self.sector) % 26 + offset).unwrap()
+                    rotate(c, self.sector)
                 } else {
                     ' '
                 }
@@ -43,24 +72,35 @@ impl Room {
     }
 }
 
+/// The real rooms whose decrypted name contains `query`, found by encrypting the query with the
+/// sector of each room rather than decrypting every name
+fn search<'a>(rooms: &'a [Room], query: &'a str) -> impl Iterator<Item = &'a Room> {
+    rooms
+        .iter()
+        .filter(|r| r.is_valid())
+        .filter(move |r| encrypt_name(query, r.sector).is_some_and(|q| r.name.contains(&q)))
+}
+
 type Input = Vec<Room>;
 
 #[aoc_generator(day4)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let l = l.trim();
-            let (l, checksum) = l.trim_end_matches(']').split_once('[').unwrap();
-            let checksum = checksum.chars().collect();
-            let (l, sector) = l.rsplit_once('-').unwrap();
-            let sector = sector.parse().unwrap();
+            let l = source.strip_suffix(l, "]")?;
+            let (l, checksum) = source.split_once(l, "[")?;
+            let checksum = checksum.to_string();
+            let (l, sector) = l.rsplit_once('-').ok_or_else(|| source.error(l, "[-]"))?;
+            let sector = source.parse(sector, "a sector id")?;
             let name = l.to_string();
-            Room {
+            Ok(Room {
                 name,
                 sector,
                 checksum,
-            }
+            })
         })
         .collect()
 }
@@ -74,12 +114,17 @@ fn part1(input: &Input) -> u32 {
         .sum()
 }
 
+/// The sector of the room where the North Pole objects are stored
 #[aoc(day4, part2)]
-fn part2(input: &Input) -> usize {
-    for room in input {
-        println!("{}: {}", room.sector, room.decrypt_name());
+fn part2(input: &Input) -> u32 {
+    for room in input.iter().filter(|r| r.is_valid()) {
+        debug!("{}: {}", room.sector, room.decrypt_name());
     }
-    input.len()
+    let room = search(input, STORAGE)
+        .next()
+        .expect("The objects are stored in a real room");
+    info!("{}: {}", room.sector, room.decrypt_name());
+    room.sector
 }
 
 #[cfg(test)]
@@ -89,12 +134,15 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "aaaaa-bbb-z-y-x-123[abxyz]
+            part1(
+                &parse(
+                    "aaaaa-bbb-z-y-x-123[abxyz]
                  a-b-c-d-e-f-g-h-987[abcde]
                  not-a-real-room-404[oarel]
                  totally-real-room-200[decoy]"
-            )),
+                )
+                .unwrap()
+            ),
             1514
         );
     }
@@ -104,9 +152,48 @@ mod tests {
         let room = Room {
             name: "qzmt-zixmtkozy-ivhz".into(),
             sector: 343,
-            checksum: vec![],
+            checksum: String::new(),
         };
 
         assert_eq!(room.decrypt_name(), String::from("very encrypted name"));
+
+        // A decoy, the storage, and the storage again in a room with a wrong checksum
+        let rooms = parse(
+            "rsqcm-cpxsqh-ghcfous-12[cshqf]
+             ghkmaihex-hucxvm-lmhktzx-501[hmxka]
+             dehjxfebu-erzusj-ijehqwu-400[abcde]",
+        )
+        .unwrap();
+        let found: Vec<_> = search(&rooms, "object").map(|r| r.sector).collect();
+        assert_eq!(found, [12, 501]);
+        for query in ["North", "northpole-object", "object storage!"] {
+            assert_eq!(search(&rooms, query).count(), 0, "{query}");
+        }
+        assert_eq!(part2(&rooms), 501);
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let name = encrypt_name("very encrypted name", 343).unwrap();
+        assert_eq!(name, "qzmt-zixmtkozy-ivhz");
+        let room = Room {
+            checksum: Room::checksum_of(&name),
+            name,
+            sector: 343,
+        };
+        assert_eq!(room.decrypt_name(), "very encrypted name");
+        assert!(room.is_valid());
+        assert_eq!(encrypt_name("north-pole", 343), None);
+        assert_eq!(encrypt_name("North", 343), None);
+
+        for (line, checksum) in [
+            ("aaaaa-bbb-z-y-x", "abxyz"),
+            ("a-b-c-d-e-f-g-h", "abcde"),
+            ("not-a-real-room", "oarel"),
+            ("totally-real-room", "loart"),
+        ] {
+            assert_eq!(Room::checksum_of(line), checksum);
+        }
+        assert_eq!(Room::checksum_of("ab-ba"), "ab");
     }
 }
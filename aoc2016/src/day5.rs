@@ -1,7 +1,10 @@
+use aoc_common::cancel::checkpoint;
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::{debug, trace};
 
 #[aoc_generator(day5)]
 fn parse(input: &str) -> String {
+    aoc_common::logging::init();
     input.to_string()
 }
 
@@ -11,14 +14,14 @@ fn part1(input: &str) -> String {
     let mut i = 0;
 
     while password.len() < 8 {
+        checkpoint();
         let val = format!("{input}{i}");
-        eprint!("string:    {val}");
-        let digest = md5::compute(val);
+        let digest = md5::compute(&val);
         let hash = format!("{digest:x}");
-        eprintln!("hash:    {hash}");
+        trace!("string: {val}, hash: {hash}");
         if hash.starts_with("00000") {
             password.push(hash.chars().nth(5).unwrap());
-            eprintln!("password:  {password}");
+            debug!("password: {password}");
         }
         i += 1;
     }
@@ -32,11 +35,11 @@ fn part2(input: &str) -> String {
     let mut i = 0;
 
     while password.contains('_') {
+        checkpoint();
         let val = format!("{input}{i}");
-        eprint!("string:    {val}     ");
-        let digest = md5::compute(val);
+        let digest = md5::compute(&val);
         let hash = format!("{digest:x}");
-        //eprintln!("hash:    {hash}");
+        trace!("string: {val}, hash: {hash}");
         if hash.starts_with("00000") {
             let pos = hash.chars().nth(5).unwrap();
             if pos.is_ascii_digit() {
@@ -46,10 +49,10 @@ fn part2(input: &str) -> String {
                         pos as usize..=pos as usize,
                         &hash.chars().nth(6).unwrap().to_string(),
                     );
+                    debug!("password: {password}");
                 }
             }
         }
-        eprintln!("password:  {password}");
         i += 1;
     }
 
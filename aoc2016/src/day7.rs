@@ -117,8 +117,7 @@ mod tests {
             ("aaaa[qwer]tyui", false),
             ("ioxxoj[asdfgh]zxcvbn", true),
         ] {
-            eprintln!("testing: [{input}]");
-            assert_eq!(output, supports_tls(input));
+            assert_eq!(output, supports_tls(input), "testing: [{input}]");
         }
     }
 
@@ -130,8 +129,7 @@ mod tests {
             ("aaa[kek]eke", true),
             ("zazbz[bzb]cdb", true),
         ] {
-            eprintln!("testing: [{input}]");
-            assert_eq!(output, supports_ssl(input));
+            assert_eq!(output, supports_ssl(input), "testing: [{input}]");
         }
     }
 }
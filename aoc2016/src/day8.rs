@@ -1,4 +1,8 @@
+use aoc_common::ocr;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::render::{Frame, Recorder, MONOCHROME};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::trace;
 
 enum Command {
     Rect { x: usize, y: usize },
@@ -9,52 +13,33 @@ enum Command {
 type Input = Vec<Command>;
 
 #[aoc_generator(day8)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let mut words = l.trim().split_ascii_whitespace();
-            match words.next().unwrap() {
-                "rect" => {
-                    let dmns = words.next().unwrap();
-                    let (x, y) = dmns.split_once('x').unwrap();
-                    Command::Rect {
-                        x: x.parse().unwrap(),
-                        y: y.parse().unwrap(),
-                    }
-                }
-                "rotate" => {
-                    match words.next().unwrap() {
-                        "row" => {
-                            let y = words
-                                .next()
-                                .unwrap()
-                                .trim_start_matches("y=")
-                                .parse()
-                                .unwrap();
-                            let _ = words.next(); // "by"
-                            let dist = words.next().unwrap().parse().unwrap();
-                            Command::RotateRow { y, dist }
-                        }
-                        "column" => {
-                            let x = words
-                                .next()
-                                .unwrap()
-                                .trim_start_matches("x=")
-                                .parse()
-                                .unwrap();
-                            let _ = words.next(); // "by"
-                            let dist = words.next().unwrap().parse().unwrap();
-                            Command::RotateCol { x, dist }
-                        }
-                        _ => {
-                            unimplemented!()
-                        }
-                    }
-                }
-                _ => {
-                    unimplemented!()
-                }
+            let mut words = source.words(l);
+            if words.one_of(&["rect", "rotate"])? == "rect" {
+                let (x, y) = source.split_once(words.next("dimensions")?, "x")?;
+                return Ok(Command::Rect {
+                    x: source.parse(x, "a width")?,
+                    y: source.parse(y, "a height")?,
+                });
+            }
+
+            if words.one_of(&["row", "column"])? == "row" {
+                let y = source.strip_prefix(words.next("a row")?, "y=")?;
+                let y = source.parse(y, "a row")?;
+                words.expect("by")?;
+                let dist = words.parse("a distance")?;
+                Ok(Command::RotateRow { y, dist })
+            } else {
+                let x = source.strip_prefix(words.next("a column")?, "x=")?;
+                let x = source.parse(x, "a column")?;
+                words.expect("by")?;
+                let dist = words.parse("a distance")?;
+                Ok(Command::RotateCol { x, dist })
             }
         })
         .collect()
@@ -101,23 +86,43 @@ impl Command {
     }
 }
 
+fn display(grid: &Grid) -> String {
+    grid.iter()
+        .map(|r| {
+            r.iter()
+                .map(|p| if *p { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[aoc(day8, part1)]
 fn part1(input: &Input) -> usize {
     let mut grid = [[false; 50]; 6];
+    let mut recorder = Recorder::from_env(module_path!(), MONOCHROME);
+    let frame = |grid: &Grid| Frame::new(50, 6, |x, y| u8::from(grid[y][x]));
+    recorder.record(|| frame(&grid));
     for cmd in input {
         cmd.run(&mut grid);
-
-        println!();
-        for r in grid {
-            for p in r {
-                print!("{}", if p { '#' } else { '.' });
-            }
-            println!();
-        }
+        trace!("\n{}", display(&grid));
+        recorder.record(|| frame(&grid));
     }
+    recorder.finish();
     grid.iter().map(|y| y.iter().filter(|x| **x).count()).sum()
 }
 
+/// The letters on the screen, or the screen itself if some letter is not known
+#[aoc(day8, part2)]
+fn part2(input: &Input) -> String {
+    let mut grid = [[false; 50]; 6];
+    for cmd in input {
+        cmd.run(&mut grid);
+    }
+    let lit = |x: usize, y: usize| grid[y][x];
+    ocr::read(50, lit).unwrap_or_else(|| format!("\n{}", display(&grid)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,13 +130,26 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "rect 3x2
+            part1(
+                &parse(
+                    "rect 3x2
                  rotate column x=1 by 1
                  rotate row y=0 by 4
                  rotate column x=1 by 1"
-            )),
+                )
+                .unwrap()
+            ),
             6
         );
     }
+
+    #[test]
+    fn part2_unknown_letters() {
+        let input = parse("rect 3x2\nrotate column x=1 by 1").unwrap();
+        let screen = part2(&input);
+        assert_eq!(
+            screen.lines().nth(1),
+            Some(format!("#.#{}", ".".repeat(47)).as_str())
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 #[aoc_generator(day9)]
 fn parse(input: &str) -> Vec<u8> {
+    aoc_common::logging::init();
     input.as_bytes().to_vec()
 }
 
@@ -33,7 +35,7 @@ fn part1(input: &[u8]) -> usize {
             idx += 1;
         }
     }
-    println!("{}", std::str::from_utf8(&decompressed).unwrap());
+    debug!("{}", String::from_utf8_lossy(&decompressed));
     decompressed.len()
 }
 
@@ -82,8 +84,7 @@ mod tests {
             ("(6x1)(1x3)A", 6),
             ("X(8x2)(3x3)ABCY", 18),
         ] {
-            eprintln!("input: {input}");
-            assert_eq!(part1(&parse(input)), output);
+            assert_eq!(part1(&parse(input)), output, "input: {input}");
         }
     }
 
@@ -98,8 +99,7 @@ mod tests {
                 445,
             ),
         ] {
-            eprintln!("input: {input}");
-            assert_eq!(part2(&parse(input)), output);
+            assert_eq!(part2(&parse(input)), output, "input: {input}");
         }
     }
 }
@@ -1,17 +1,14 @@
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Input = Vec<Vec<u32>>;
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
-        .map(|l| {
-            l.split_whitespace()
-                .map(str::parse::<u32>)
-                .filter_map(Result::ok)
-                .collect()
-        })
+        .map(|l| source.whitespace_separated(l))
         .collect()
 }
 
@@ -67,11 +64,14 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "5 1 9 5
+            part1(
+                &parse(
+                    "5 1 9 5
                  7 5 3
                  2 4 6 8"
-            )),
+                )
+                .unwrap()
+            ),
             18
         );
     }
@@ -79,11 +79,14 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "5 9 2 8
+            part2(
+                &parse(
+                    "5 9 2 8
                  9 4 7 3
                  3 8 6 5"
-            )),
+                )
+                .unwrap()
+            ),
             9
         );
     }
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 #[aoc_generator(day3)]
-fn parse(input: &str) -> i32 {
-    input.parse().unwrap_or(0)
+fn parse(input: &str) -> Result<i32, ParseError> {
+    aoc_common::logging::init();
+    Source::new(input).parse(input.trim(), "a square")
 }
 
 /// Determine position of integer within grid
@@ -40,7 +43,7 @@ fn part1(input: &i32) -> i32 {
     }
     // determine coords of input cell
     let (x, y) = grid_pos(*input - 1);
-    dbg!(x, y);
+    debug!("Square {input} is at ({x}, {y})");
     // calculate distance to 0,0
     x.abs() + y.abs()
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 struct Claim {
@@ -13,43 +14,25 @@ struct Claim {
 type Input = Vec<Claim>;
 
 #[aoc_generator(day3)]
-fn parse(input: &str) -> Input {
-    input
-        .trim()
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let mut tokens = l.trim().split_ascii_whitespace();
-            let id = tokens
-                .next()
-                .unwrap()
-                .trim_start_matches('#')
-                .parse()
-                .unwrap();
-            let _ = tokens.next();
-            let mut pos = tokens
-                .next()
-                .unwrap()
-                .trim_end_matches(':')
-                .split(',')
-                .map(str::parse)
-                .filter_map(Result::ok);
-            let left = pos.next().unwrap();
-            let top = pos.next().unwrap();
-            let mut dims = tokens
-                .next()
-                .unwrap()
-                .split('x')
-                .map(str::parse)
-                .filter_map(Result::ok);
-            let width = dims.next().unwrap();
-            let height = dims.next().unwrap();
-            Claim {
-                id,
-                left,
-                top,
-                width,
-                height,
-            }
+            // #1 @ 1,3: 4x4
+            let mut tokens = source.words(l);
+            let id = source.strip_prefix(tokens.next("a claim id")?, "#")?;
+            tokens.expect("@")?;
+            let pos = source.strip_suffix(tokens.next("a position")?, ":")?;
+            let (left, top) = source.split_once(pos, ",")?;
+            let (width, height) = source.split_once(tokens.next("a size")?, "x")?;
+            Ok(Claim {
+                id: source.parse(id, "a claim id")?,
+                left: source.parse(left, "a left edge")?,
+                top: source.parse(top, "a top edge")?,
+                width: source.parse(width, "a width")?,
+                height: source.parse(height, "a height")?,
+            })
         })
         .collect()
 }
@@ -101,11 +84,14 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "#1 @ 1,3: 4x4
+            part1(
+                &parse(
+                    "#1 @ 1,3: 4x4
                  #2 @ 3,1: 4x4
                  #3 @ 5,5: 2x2"
-            )),
+                )
+                .unwrap()
+            ),
             4
         );
     }
@@ -113,11 +99,14 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "#1 @ 1,3: 4x4
+            part2(
+                &parse(
+                    "#1 @ 1,3: 4x4
                  #2 @ 3,1: 4x4
                  #3 @ 5,5: 2x2"
-            )),
+                )
+                .unwrap()
+            ),
             3
         );
     }
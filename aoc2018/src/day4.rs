@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 use jiff::civil::DateTime;
 
@@ -21,20 +22,26 @@ struct Event {
 }
 
 impl Event {
-    fn parse(l: &str, mut guard_id: GuardId) -> Option<Self> {
-        let (date_str, l) = l.trim_start().trim_start_matches('[').split_once("] ")?;
-        let date = date_str.parse().ok()?;
-        let mut words_iter = l.split_ascii_whitespace();
-        let event = match words_iter.next()? {
+    fn parse<'a>(
+        source: &Source<'a>,
+        l: &'a str,
+        mut guard_id: GuardId,
+    ) -> Result<Self, ParseError> {
+        let l = source.strip_prefix(l, "[")?;
+        let (date_str, l) = source.split_once(l, "] ")?;
+        let date = source.parse(date_str, "a date")?;
+        let mut words = source.words(l);
+        let event = match words.one_of(&["Guard", "falls", "wakes"])? {
             "Guard" => {
-                guard_id = words_iter.next()?.trim_start_matches('#').parse().ok()?;
+                let id = source.strip_prefix(words.next("a guard id")?, "#")?;
+                guard_id = source.parse(id, "a guard id")?;
                 GuardEvent::Begin
             }
             "wakes" => GuardEvent::Wake,
             _ => GuardEvent::Sleep,
         };
 
-        Some(Self {
+        Ok(Self {
             date,
             guard_id,
             event,
@@ -43,16 +50,16 @@ impl Event {
 }
 
 #[aoc_generator(day4)]
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
     let mut guard_id = 0;
     let mut evts = vec![];
-    for line in input.lines() {
-        if let Some(evt) = Event::parse(line, guard_id) {
-            guard_id = evt.guard_id;
-            evts.push(evt);
-        }
+    for line in source.lines() {
+        let evt = Event::parse(&source, line, guard_id)?;
+        guard_id = evt.guard_id;
+        evts.push(evt);
     }
-    evts
+    Ok(evts)
 }
 
 fn guard_sleep_spans(input: &Input) -> HashMap<u32, Vec<(DateTime, DateTime)>> {
@@ -154,25 +161,7 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "[1518-11-01 00:00] Guard #10 begins shift
-                 [1518-11-01 00:05] falls asleep
-                 [1518-11-01 00:25] wakes up
-                 [1518-11-01 00:30] falls asleep
-                 [1518-11-01 00:55] wakes up
-                 [1518-11-01 23:58] Guard #99 begins shift
-                 [1518-11-02 00:40] falls asleep
-                 [1518-11-02 00:50] wakes up
-                 [1518-11-03 00:05] Guard #10 begins shift
-                 [1518-11-03 00:24] falls asleep
-                 [1518-11-03 00:29] wakes up
-                 [1518-11-04 00:02] Guard #99 begins shift
-                 [1518-11-04 00:36] falls asleep
-                 [1518-11-04 00:46] wakes up
-                 [1518-11-05 00:03] Guard #99 begins shift
-                 [1518-11-05 00:45] falls asleep
-                 [1518-11-05 00:55] wakes up"
-            )),
+            part1(&parse(include_str!("../examples/day4/1.txt")).unwrap()),
             240
         );
     }
@@ -180,25 +169,7 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "[1518-11-01 00:00] Guard #10 begins shift
-                 [1518-11-01 00:05] falls asleep
-                 [1518-11-01 00:25] wakes up
-                 [1518-11-01 00:30] falls asleep
-                 [1518-11-01 00:55] wakes up
-                 [1518-11-01 23:58] Guard #99 begins shift
-                 [1518-11-02 00:40] falls asleep
-                 [1518-11-02 00:50] wakes up
-                 [1518-11-03 00:05] Guard #10 begins shift
-                 [1518-11-03 00:24] falls asleep
-                 [1518-11-03 00:29] wakes up
-                 [1518-11-04 00:02] Guard #99 begins shift
-                 [1518-11-04 00:36] falls asleep
-                 [1518-11-04 00:46] wakes up
-                 [1518-11-05 00:03] Guard #99 begins shift
-                 [1518-11-05 00:45] falls asleep
-                 [1518-11-05 00:55] wakes up"
-            )),
+            part2(&parse(include_str!("../examples/day4/1.txt")).unwrap()),
             4455
         );
     }
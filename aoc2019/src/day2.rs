@@ -1,12 +1,9 @@
+use aoc_common::parse::{comma_separated, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Vec<usize> {
-    input
-        .split(',')
-        .map(str::parse)
-        .filter_map(Result::ok)
-        .collect()
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    comma_separated(input)
 }
 
 /// Run an Intcode program
@@ -100,7 +97,7 @@ mod tests {
             ("2,4,4,5,99,0", "2,4,4,5,99,9801"),
             ("1,1,1,4,99,5,6,0,99", "30,1,1,4,2,5,6,0,99"),
         ] {
-            let prg = run_program(parse(input));
+            let prg = run_program(parse(input).unwrap());
             let prg = prg
                 .iter()
                 .map(usize::to_string)
@@ -1,15 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::{Direction, Point};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Right,
-    Left,
-}
-
 #[derive(Debug, Clone, Copy)]
 struct Vector {
     dir: Direction,
@@ -17,22 +11,15 @@ struct Vector {
 }
 
 type Wire = Vec<Vector>;
-type Position = (i32, i32);
 
-fn wire_positions(wire: &Wire) -> Vec<Position> {
+fn wire_positions(wire: &Wire) -> Vec<Point> {
     let mut positions = vec![];
-    let mut pos = (0, 0);
+    let mut pos = Point::ORIGIN;
     for v in wire {
         //dbg!(v);
         // Add all positions to vec
         for _ in 0..v.len {
-            let (x, y) = pos;
-            pos = match v.dir {
-                Direction::Up => (x, y + 1),
-                Direction::Down => (x, y - 1),
-                Direction::Right => (x + 1, y),
-                Direction::Left => (x - 1, y),
-            };
+            pos = pos.step(v.dir);
             //dbg!(pos);
             positions.push(pos);
         }
@@ -46,30 +33,32 @@ struct Input {
 }
 
 #[aoc_generator(day3)]
-fn parse(input: &str) -> Input {
-    let mut wires = input.trim().lines().map(|l| {
-        l.trim()
-            .split(',')
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let mut wires = source.lines().map(|l| {
+        l.split(',')
             .map(|v_str| {
-                let dir = match v_str.chars().next().unwrap() {
-                    'U' => Direction::Up,
-                    'D' => Direction::Down,
-                    'R' => Direction::Right,
-                    'L' => Direction::Left,
-                    _ => unimplemented!(),
-                };
+                let mut chars = v_str.chars();
+                let dir = chars
+                    .next()
+                    .and_then(Direction::from_char)
+                    .ok_or_else(|| source.error(v_str, "[U], [D], [L] or [R]"))?;
 
-                let len = v_str[1..].parse().unwrap();
+                let len = source.parse(chars.as_str(), "a length")?;
 
-                Vector { dir, len }
+                Ok(Vector { dir, len })
             })
-            .collect()
+            .collect::<Result<Wire, _>>()
     });
 
-    let wire_a = wires.next().unwrap();
-    let wire_b = wires.next().unwrap();
+    let wire_a = wires
+        .next()
+        .ok_or_else(|| source.end_error(input, "a wire"))??;
+    let wire_b = wires
+        .next()
+        .ok_or_else(|| source.end_error(input, "a second wire"))??;
 
-    Input { wire_a, wire_b }
+    Ok(Input { wire_a, wire_b })
 }
 
 #[aoc(day3, part1)]
@@ -83,8 +72,7 @@ fn part1(input: &Input) -> i32 {
     for pos in wire_positions(&input.wire_b) {
         //dbg!(pos);
         if a_ps.contains(&pos) {
-            let (x, y) = pos;
-            nearest = nearest.min(x.abs() + y.abs());
+            nearest = nearest.min(pos.manhattan());
             //dbg!(nearest);
         }
     }
@@ -130,7 +118,7 @@ mod tests {
                 135,
             ),
         ] {
-            assert_eq!(part1(&parse(input)), output);
+            assert_eq!(part1(&parse(input).unwrap()), output);
         }
     }
 
@@ -153,7 +141,7 @@ mod tests {
                 410,
             ),
         ] {
-            assert_eq!(part2(&parse(input)), output);
+            assert_eq!(part2(&parse(input).unwrap()), output);
         }
     }
 }
@@ -1,13 +1,20 @@
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Input = (u32, u32);
 
 #[aoc_generator(day4)]
-fn parse(input: &str) -> Input {
-    let (start, finish) = input.split_once('-').unwrap();
-    (start.parse().unwrap(), finish.parse().unwrap())
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let (start, finish) = source.split_once(input.trim(), "-")?;
+    Ok((
+        source.parse(start, "a number")?,
+        source.parse(finish, "a number")?,
+    ))
 }
 
 fn check_password(password: u32) -> bool {
@@ -33,8 +40,8 @@ fn check_password(password: u32) -> bool {
     has_repeating
 }
 
-#[aoc(day4, part1)]
-fn part1(input: &Input) -> usize {
+#[aoc(day4, part1, brute_force)]
+fn part1_brute_force(input: &Input) -> usize {
     (input.0..input.1).filter(|p| check_password(*p)).count()
 }
 
@@ -61,28 +68,310 @@ fn check_password2(password: u32) -> bool {
     repeating.values().any(|c| *c == 2)
 }
 
-#[aoc(day4, part2)]
-fn part2(input: &Input) -> usize {
+#[aoc(day4, part2, brute_force)]
+fn part2_brute_force(input: &Input) -> usize {
     (input.0..input.1).filter(|p| check_password2(*p)).count()
 }
 
+/// A password rule, expressed as a state machine that is fed the digits of a
+/// number from most to least significant
+trait Rule {
+    type State: Copy + Eq + Hash;
+
+    /// The state before any digit has been seen
+    fn start(&self) -> Self::State;
+
+    /// Feed the next digit. Returns `None` if the number can no longer satisfy the rule.
+    fn next(&self, state: Self::State, digit: u8) -> Option<Self::State>;
+
+    /// Whether the number fed so far satisfies the rule
+    fn accepts(&self, state: Self::State) -> bool;
+
+    /// Combine this rule with another one, both of which must hold
+    fn and<R: Rule>(self, other: R) -> And<Self, R>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+}
+
+/// Digits never decrease from left to right
+struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    type State = Option<u8>;
+
+    fn start(&self) -> Self::State {
+        None
+    }
+
+    fn next(&self, prev: Self::State, digit: u8) -> Option<Self::State> {
+        match prev {
+            Some(p) if p > digit => None,
+            _ => Some(Some(digit)),
+        }
+    }
+
+    fn accepts(&self, _: Self::State) -> bool {
+        true
+    }
+}
+
+/// Progress through a run of repeated digits
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Run {
+    digit: Option<u8>,
+    /// Length of the current run, capped at 3 since no rule needs to tell longer runs apart
+    len: u8,
+    found: bool,
+}
+
+impl Run {
+    fn start() -> Self {
+        Self {
+            digit: None,
+            len: 0,
+            found: false,
+        }
+    }
+
+    /// Extend the run with `digit`, calling `done` with the length of the run it ends, if any
+    fn next(self, digit: u8, done: impl Fn(u8) -> bool) -> Self {
+        if self.digit == Some(digit) {
+            Self {
+                len: (self.len + 1).min(3),
+                ..self
+            }
+        } else {
+            Self {
+                digit: Some(digit),
+                len: 1,
+                found: self.found || done(self.len),
+            }
+        }
+    }
+}
+
+/// At least two adjacent digits are the same
+struct HasPair;
+
+impl Rule for HasPair {
+    type State = Run;
+
+    fn start(&self) -> Self::State {
+        Run::start()
+    }
+
+    fn next(&self, run: Self::State, digit: u8) -> Option<Self::State> {
+        Some(run.next(digit, |len| len >= 2))
+    }
+
+    fn accepts(&self, run: Self::State) -> bool {
+        run.found || run.len >= 2
+    }
+}
+
+/// Some digit is repeated exactly twice in a row, and not as part of a larger group
+struct HasExactPair;
+
+impl Rule for HasExactPair {
+    type State = Run;
+
+    fn start(&self) -> Self::State {
+        Run::start()
+    }
+
+    fn next(&self, run: Self::State, digit: u8) -> Option<Self::State> {
+        Some(run.next(digit, |len| len == 2))
+    }
+
+    fn accepts(&self, run: Self::State) -> bool {
+        run.found || run.len == 2
+    }
+}
+
+/// Both rules must hold
+struct And<A, B>(A, B);
+
+impl<A: Rule, B: Rule> Rule for And<A, B> {
+    type State = (A::State, B::State);
+
+    fn start(&self) -> Self::State {
+        (self.0.start(), self.1.start())
+    }
+
+    fn next(&self, (a, b): Self::State, digit: u8) -> Option<Self::State> {
+        Some((self.0.next(a, digit)?, self.1.next(b, digit)?))
+    }
+
+    fn accepts(&self, (a, b): Self::State) -> bool {
+        self.0.accepts(a) && self.1.accepts(b)
+    }
+}
+
+/// Count the positive numbers up to and including `max` that satisfy `rule`
+fn count_up_to<R: Rule>(max: u64, rule: &R) -> u64 {
+    /// Count the ways to fill in the digits from `pos` onwards. A `None` state means only
+    /// leading zeros have been seen so far.
+    fn go<R: Rule>(
+        digits: &[u8],
+        pos: usize,
+        state: Option<R::State>,
+        tight: bool,
+        rule: &R,
+        cache: &mut HashMap<(usize, Option<R::State>), u64>,
+    ) -> u64 {
+        if pos == digits.len() {
+            return u64::from(state.is_some_and(|s| rule.accepts(s)));
+        }
+        if !tight {
+            if let Some(count) = cache.get(&(pos, state)) {
+                return *count;
+            }
+        }
+
+        let limit = if tight { digits[pos] } else { 9 };
+        let mut count = 0;
+        for digit in 0..=limit {
+            let next = match state {
+                None if digit == 0 => Some(None),
+                None => rule.next(rule.start(), digit).map(Some),
+                Some(s) => rule.next(s, digit).map(Some),
+            };
+            if let Some(next) = next {
+                count += go(digits, pos + 1, next, tight && digit == limit, rule, cache);
+            }
+        }
+
+        if !tight {
+            cache.insert((pos, state), count);
+        }
+        count
+    }
+
+    let digits: Vec<u8> = max.to_string().bytes().map(|b| b - b'0').collect();
+    go(&digits, 0, None, true, rule, &mut HashMap::new())
+}
+
+/// Count the positive numbers within `range` that satisfy `rule`, without visiting each one
+fn count_passwords<R: Rule>(range: impl RangeBounds<u64>, rule: &R) -> u64 {
+    let start = match range.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(e) => *e,
+        Bound::Excluded(0) => return 0,
+        Bound::Excluded(e) => e - 1,
+        Bound::Unbounded => u64::MAX,
+    };
+    if start > end {
+        return 0;
+    }
+
+    count_up_to(end, rule) - start.checked_sub(1).map_or(0, |s| count_up_to(s, rule))
+}
+
+/// The passwords of the puzzle are six-digit numbers
+const SIX_DIGITS: RangeInclusive<u64> = 100_000..=999_999;
+
+/// Count the six-digit numbers within the puzzle range that satisfy `rule`
+fn count_six_digits<R: Rule>(input: &Input, rule: &R) -> u64 {
+    let start = u64::from(input.0).max(*SIX_DIGITS.start());
+    let end = u64::from(input.1).min(*SIX_DIGITS.end() + 1);
+    count_passwords(start..end, rule)
+}
+
+#[aoc(day4, part1)]
+fn part1(input: &Input) -> u64 {
+    count_six_digits(input, &NonDecreasing.and(HasPair))
+}
+
+#[aoc(day4, part2)]
+fn part2(input: &Input) -> u64 {
+    count_six_digits(input, &NonDecreasing.and(HasExactPair))
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    /// Check a single number against a rule by feeding it its digits
+    fn satisfies<R: Rule>(n: u64, rule: &R) -> bool {
+        n.to_string()
+            .bytes()
+            .try_fold(rule.start(), |s, b| rule.next(s, b - b'0'))
+            .is_some_and(|s| rule.accepts(s))
+    }
+
     #[test]
     fn test_check_password() {
         for (input, output) in [(111111, true), (223450, false), (123789, false)] {
-            eprintln!("input: {input} output: {output}");
-            assert_eq!(output, check_password(input));
+            assert_eq!(
+                output,
+                check_password(input),
+                "input: {input} output: {output}"
+            );
         }
     }
 
     #[test]
     fn test_check_password2() {
         for (input, output) in [(112233, true), (123444, false), (111122, true)] {
-            eprintln!("input: {input} output: {output}");
-            assert_eq!(output, check_password2(input));
+            assert_eq!(
+                output,
+                check_password2(input),
+                "input: {input} output: {output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_passwords() {
+        let rule = NonDecreasing.and(HasPair);
+        for (range, output) in [
+            (1..=9, 0),
+            (1..=11, 1),
+            (10..=99, 9),
+            (111_111..=111_111, 1),
+        ] {
+            assert_eq!(
+                output,
+                count_passwords(range.clone(), &rule),
+                "range: {range:?} output: {output}"
+            );
+        }
+        assert_eq!(0, count_passwords(5..5, &rule));
+        assert_eq!(0, count_passwords(0..0, &rule));
+    }
+
+    #[test]
+    fn test_count_passwords_large_range() {
+        // Non-decreasing numbers of up to 19 digits without a repeated digit choose a subset of
+        // 1..=9, so every other non-decreasing number has a pair
+        let non_decreasing = count_passwords(1..10_u64.pow(19), &NonDecreasing);
+        let with_pair = count_passwords(1..10_u64.pow(19), &NonDecreasing.and(HasPair));
+        assert_eq!(non_decreasing - with_pair, 2_u64.pow(9) - 1);
+        assert!(count_passwords(.., &NonDecreasing.and(HasExactPair)) > 0);
+    }
+
+    proptest! {
+        #[test]
+        fn count_matches_brute_force(a in 0_u32..1_100_000, len in 0_u32..20_000) {
+            let input = (a, a + len);
+            prop_assert_eq!(part1(&input), part1_brute_force(&input) as u64);
+            prop_assert_eq!(part2(&input), part2_brute_force(&input) as u64);
+        }
+
+        #[test]
+        fn count_matches_rules(a in 0_u64..200_000, len in 0_u64..5_000) {
+            let rule = NonDecreasing.and(HasExactPair).and(HasPair);
+            let expected = (a..=a + len).filter(|n| *n > 0 && satisfies(*n, &rule)).count();
+            prop_assert_eq!(count_passwords(a..=a + len, &rule), expected as u64);
         }
     }
 }
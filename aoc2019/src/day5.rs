@@ -1,10 +1,11 @@
+use aoc_common::parse::{comma_separated, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 use crate::intcode::{Intcode, State};
 
 #[aoc_generator(day5)]
-pub fn parse(input: &str) -> Intcode {
-    Intcode::new(input.split(',').map(|s| s.parse().unwrap()).collect())
+pub fn parse(input: &str) -> Result<Intcode, ParseError> {
+    comma_separated(input).map(Intcode::new)
 }
 
 fn run_program(prg: &mut Intcode, input: i64) -> Option<i64> {
@@ -42,9 +43,12 @@ mod tests {
             ("1002,4,3,4,33", 5, None),
             ("1101,100,-1,4,0", 5, None),
         ] {
-            eprintln!("Program: {program}, input: {input}");
-            let mut prg = parse(program);
-            assert_eq!(output, run_program(&mut prg, input));
+            let mut prg = parse(program).unwrap();
+            assert_eq!(
+                output,
+                run_program(&mut prg, input),
+                "Program: {program}, input: {input}"
+            );
         }
     }
 
@@ -76,9 +80,12 @@ mod tests {
             ("3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99", 8,1000),
             ("3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99", 9,1001),
         ] {
-            eprintln!("Program: {program}, input: {input}");
-            let mut prg = parse(program);
-            assert_eq!(run_program(&mut prg, input).unwrap(), output);
+            let mut prg = parse(program).unwrap();
+            assert_eq!(
+                run_program(&mut prg, input).unwrap(),
+                output,
+                "Program: {program}, input: {input}"
+            );
         }
     }
 }
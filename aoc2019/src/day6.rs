@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Target = String;
@@ -7,12 +8,13 @@ type Object = String;
 type Input = Vec<(Target, Object)>;
 
 #[aoc_generator(day6)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let (target, object) = l.trim().split_once(')').unwrap();
-            (target.to_string(), object.to_string())
+            let (target, object) = source.split_once(l, ")")?;
+            Ok((target.to_string(), object.to_string()))
         })
         .collect()
 }
@@ -75,8 +77,9 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "COM)B
+            part1(
+                &parse(
+                    "COM)B
                  B)C
                  C)D
                  D)E
@@ -87,7 +90,9 @@ mod tests {
                  E)J
                  J)K
                  K)L"
-            )),
+                )
+                .unwrap()
+            ),
             42
         );
     }
@@ -95,8 +100,9 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "COM)B
+            part2(
+                &parse(
+                    "COM)B
                  B)C
                  C)D
                  D)E
@@ -109,7 +115,9 @@ mod tests {
                  K)L
                  K)YOU
                  I)SAN"
-            )),
+                )
+                .unwrap()
+            ),
             4
         );
     }
@@ -4,6 +4,7 @@ use std::{
     rc::{Rc, Weak},
 };
 
+use aoc_common::parse::{comma_separated, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
@@ -12,8 +13,8 @@ use crate::intcode::{Intcode, State};
 type Input = Vec<i64>;
 
 #[aoc_generator(day7)]
-fn parse(input: &str) -> Input {
-    input.split(',').map(|s| s.parse().unwrap()).collect()
+fn parse(input: &str) -> Result<Input, ParseError> {
+    comma_separated(input)
 }
 
 #[aoc(day7, part1)]
@@ -163,8 +164,7 @@ mod tests {
             (54321, "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0"),
             (65210, "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0"),
         ] {
-            eprintln!("Program: {program}, signal: {signal}");
-            assert_eq!(part1(&parse(program)), signal);
+            assert_eq!(part1(&parse(program).unwrap()), signal, "Program: {program}, signal: {signal}");
         }
     }
 
@@ -174,8 +174,7 @@ mod tests {
             (139629729, "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5"),
             (18216, "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10"),
         ] {
-            eprintln!("Program: {program}, signal: {signal}");
-            assert_eq!(part2(&parse(program)), signal);
+            assert_eq!(part2(&parse(program).unwrap()), signal, "Program: {program}, signal: {signal}");
         }
     }
 }
@@ -1,3 +1,5 @@
+use aoc_common::ocr;
+use aoc_common::render::{Frame, Recorder};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[aoc_generator(day8)]
@@ -18,28 +20,28 @@ fn part1(input: &str) -> usize {
     ones * twos
 }
 
+/// The letters of the image, or the image itself if some letter is not known
 #[aoc(day8, part2)]
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> String {
     const AREA: usize = 25 * 6;
-    let layers = input.as_bytes().chunks(AREA).collect::<Vec<_>>();
+    // Black, white and transparent, shown as gray
+    let mut recorder = Recorder::from_env(
+        concat!(module_path!(), "::part2"),
+        &[[0, 0, 0], [0xff, 0xff, 0xff], [0x80, 0x80, 0x80]],
+    );
+    let frame = |layer: &[u8]| Frame::new(25, 6, |x, y| layer[y * 25 + x] - b'0');
+    // Composite the layers from the back, so that each one covers those behind it
     let mut final_layer = [b'2'; AREA];
-    for i in 0..AREA {
-        for layer in &layers {
-            if layer[i] != b'2' {
-                final_layer[i] = layer[i];
-                break;
+    recorder.record(|| frame(&final_layer));
+    for layer in input.as_bytes().chunks(AREA).rev() {
+        for (pixel, color) in final_layer.iter_mut().zip(layer) {
+            if *color != b'2' {
+                *pixel = *color;
             }
         }
+        recorder.record(|| frame(&final_layer));
     }
-    let image = final_layer
-        .chunks(25)
-        .map(|row| {
-            row.iter()
-                .map(|c| if *c == b'1' { '█' } else { ' ' })
-                .collect::<String>()
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    println!("{image}");
-    0
+    recorder.finish();
+    let lit = |x: usize, y: usize| final_layer[y * 25 + x] == b'1';
+    ocr::read(25, lit).unwrap_or_else(|| format!("\n{}", ocr::draw(25, lit)))
 }
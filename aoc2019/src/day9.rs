@@ -1,12 +1,15 @@
+use aoc_common::parse::{comma_separated, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 use crate::intcode::{Intcode, State};
 
 type Input = Vec<i64>;
 
 #[aoc_generator(day9)]
-fn parse(input: &str) -> Input {
-    input.split(',').map(|l| l.parse().unwrap()).collect()
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    comma_separated(input)
 }
 
 fn run_program(program: Input, input: Option<i64>) -> Vec<i64> {
@@ -25,14 +28,14 @@ fn run_program(program: Input, input: Option<i64>) -> Vec<i64> {
 #[aoc(day9, part1)]
 fn part1(input: &Input) -> i64 {
     let output = run_program(input.to_owned(), Some(1));
-    println!("{output:?}");
+    debug!("Output: {output:?}");
     output[0]
 }
 
 #[aoc(day9, part2)]
 fn part2(input: &Input) -> i64 {
     let output = run_program(input.to_owned(), Some(2));
-    println!("{output:?}");
+    debug!("Output: {output:?}");
     output[0]
 }
 
@@ -55,8 +58,11 @@ mod tests {
             ),
             ("104,1125899906842624,99", vec![1_125_899_906_842_624]),
         ] {
-            eprintln!("Program: {program}");
-            assert_eq!(output, run_program(parse(program), None));
+            assert_eq!(
+                output,
+                run_program(parse(program).unwrap(), None),
+                "Program: {program}"
+            );
         }
     }
 }
@@ -1,7 +1,8 @@
 use aoc2019::intcode::Intcode;
+use aoc_common::parse::comma_separated;
 
 fn main() {
     let prg = std::env::args().nth(1).expect("Must provide a source prg");
-    let asm = Intcode::new(prg.split(',').filter_map(|s| s.parse().ok()).collect()).disassemble();
+    let asm = Intcode::new(comma_separated(&prg).expect("Invalid program")).disassemble();
     println!("{asm}");
 }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::trace;
 
 type BagCount = usize;
 type BagColor = String;
@@ -8,27 +10,28 @@ type Rule = HashMap<BagColor, BagCount>;
 type Input = HashMap<BagColor, Rule>;
 
 #[aoc_generator(day7)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let (rule_str, bags_str) = l.trim().split_once(" contain ").unwrap();
-            let color = rule_str.trim_end_matches(" bags").to_string();
+            let (rule_str, bags_str) = source.split_once(l, " contain ")?;
+            let color = source.strip_suffix(rule_str, " bags")?.to_string();
+            let bags_str = source.strip_suffix(bags_str, ".")?;
+            if bags_str == "no other bags" {
+                return Ok((color, Rule::new()));
+            }
             let bags = bags_str
-                .trim_end_matches('.')
                 .split(", ")
-                .filter_map(|b| {
-                    let (qty_str, clr_str) = b
-                        .trim_end_matches('s')
-                        .trim_end_matches(" bag")
-                        .split_once(' ')
-                        .unwrap();
-                    let quantity = qty_str.parse().ok()?;
-                    let color = clr_str.to_string();
-                    Some((color, quantity))
+                .map(|b| {
+                    let b = source.strip_suffix(b.trim_end_matches('s'), " bag")?;
+                    let (qty_str, clr_str) = source.split_once(b, " ")?;
+                    let quantity = source.parse(qty_str, "a quantity")?;
+                    Ok((clr_str.to_string(), quantity))
                 })
-                .collect();
-            (color, bags)
+                .collect::<Result<_, _>>()?;
+            Ok((color, bags))
         })
         .collect()
 }
@@ -65,20 +68,20 @@ fn part1(input: &Input) -> usize {
 fn part2(input: &Input) -> usize {
     fn count_bags(bag: &BagColor, rules: &Input, i: usize) -> usize {
         let indent = "  ".repeat(i);
-        eprintln!("{indent}Counting contents of bag [{bag}]");
+        trace!("{indent}Counting contents of bag [{bag}]");
         let rule = rules.get(bag).unwrap();
         if rule.is_empty() {
-            eprintln!("{indent}No bags");
+            trace!("{indent}No bags");
             1
         } else {
             let total: usize = rule
                 .iter()
                 .map(|(color, count)| {
-                    eprintln!("{indent}color: {color}, count: {count}");
+                    trace!("{indent}color: {color}, count: {count}");
                     count * count_bags(color, rules, i + 1)
                 })
                 .sum();
-            eprintln!("{indent}bag [{bag}] total: {total}");
+            trace!("{indent}bag [{bag}] total: {total}");
             total + 1
         }
     }
@@ -92,17 +95,7 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "light red bags contain 1 bright white bag, 2 muted yellow bags.
-                 dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-                 bright white bags contain 1 shiny gold bag.
-                 muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-                 shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-                 dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-                 vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-                 faded blue bags contain no other bags.
-                 dotted black bags contain no other bags."
-            )),
+            part1(&parse(include_str!("../examples/day7/1.txt")).unwrap()),
             4
         );
     }
@@ -110,30 +103,10 @@ mod tests {
     #[test]
     fn part2_example() {
         for (input, output) in [
-            (
-                "light red bags contain 1 bright white bag, 2 muted yellow bags.
-                 dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-                 bright white bags contain 1 shiny gold bag.
-                 muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-                 shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-                 dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-                 vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-                 faded blue bags contain no other bags.
-                 dotted black bags contain no other bags.",
-                32,
-            ),
-            (
-                "shiny gold bags contain 2 dark red bags.
-                 dark red bags contain 2 dark orange bags.
-                 dark orange bags contain 2 dark yellow bags.
-                 dark yellow bags contain 2 dark green bags.
-                 dark green bags contain 2 dark blue bags.
-                 dark blue bags contain 2 dark violet bags.
-                 dark violet bags contain no other bags.",
-                126,
-            ),
+            (include_str!("../examples/day7/1.txt"), 32),
+            (include_str!("../examples/day7/2.txt"), 126),
         ] {
-            assert_eq!(part2(&parse(input)), output);
+            assert_eq!(part2(&parse(input).unwrap()), output);
         }
     }
 }
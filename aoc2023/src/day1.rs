@@ -134,28 +134,12 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(
-            part1(&parse(
-                "1abc2
-                 pqr3stu8vwx
-                 a1b2c3d4e5f
-                 treb7uchet"
-            )),
-            142
-        );
+        assert_eq!(part1(&parse(include_str!("../examples/day1/1.txt"))), 142);
     }
 
     #[test]
     fn part2_example() {
-        let parsed = &parse(
-            "two1nine
-             eightwothree
-             abcone2threexyz                                    
-             xtwone3four
-             4nineeightseven2
-             zoneight234
-             7pqrstsixteen",
-        );
+        let parsed = &parse(include_str!("../examples/day1/2.txt"));
         assert_eq!(part2(parsed), 281);
         assert_eq!(part2_correct(parsed), 281);
     }
@@ -1,3 +1,4 @@
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -21,40 +22,39 @@ struct Game {
 type Input = Vec<Game>;
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
-        .filter_map(|l| {
-            let (game_str, shows_str) = l.trim().split_once(": ")?;
+        .map(|l| {
+            let (game_str, shows_str) = source.split_once(l, ": ")?;
 
-            let id = game_str.trim_start_matches("Game ").parse().ok()?;
+            let id = source.parse(source.strip_prefix(game_str, "Game ")?, "a game id")?;
             let shows = shows_str
                 .split("; ")
                 .map(|show_str| {
                     let mut show = Show::default();
                     for color_str in show_str.split(", ") {
-                        if let Some((n, color)) = color_str.split_once(' ') {
-                            if let Ok(count) = n.parse::<u32>() {
-                                match color {
-                                    "red" => {
-                                        show.red += count;
-                                    }
-                                    "green" => {
-                                        show.green += count;
-                                    }
-                                    "blue" => {
-                                        show.blue += count;
-                                    }
-                                    _ => {}
-                                }
+                        let (n, color) = source.split_once(color_str, " ")?;
+                        let count: u32 = source.parse(n, "a count")?;
+                        match color {
+                            "red" => {
+                                show.red += count;
+                            }
+                            "green" => {
+                                show.green += count;
+                            }
+                            "blue" => {
+                                show.blue += count;
                             }
+                            _ => return Err(source.error(color, "[red], [green] or [blue]")),
                         }
                     }
-                    show
+                    Ok(show)
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
 
-            Some(Game { id, shows })
+            Ok(Game { id, shows })
         })
         .collect()
 }
@@ -97,13 +97,16 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            part1(
+                &parse(
+                    "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
                  Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
                  Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
                  Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
                  Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            )),
+                )
+                .unwrap()
+            ),
             8
         );
     }
@@ -111,13 +114,16 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            part2(
+                &parse(
+                    "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
                  Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
                  Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
                  Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
                  Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            )),
+                )
+                .unwrap()
+            ),
             2286
         );
     }
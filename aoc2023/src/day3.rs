@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Position = (usize, usize);
@@ -10,6 +11,7 @@ enum Component {
     Symbol(char),
 }
 
+#[derive(Debug)]
 struct Schematic {
     grid_len: usize,
     line_len: usize,
@@ -56,10 +58,18 @@ impl Schematic {
 }
 
 #[aoc_generator(day3)]
-fn parse(input: &str) -> Schematic {
+fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let source = Source::new(input);
     let input = input.trim();
     let grid_len = input.lines().count();
-    let line_len = input.chars().position(|c| c == '\n').unwrap();
+    let line_len = source
+        .lines()
+        .next()
+        .ok_or_else(|| source.end_error(input, "a schematic"))?
+        .len();
+    if let Some(line) = source.lines().find(|l| l.len() != line_len) {
+        return Err(source.error(line, format!("a row of {line_len} cells")));
+    }
 
     // Parse components
     let chars = input
@@ -85,11 +95,11 @@ fn parse(input: &str) -> Schematic {
         }
     }
 
-    Schematic {
+    Ok(Schematic {
         grid_len,
         line_len,
         components,
-    }
+    })
 }
 
 #[aoc(day3, part1)]
@@ -128,8 +138,9 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "467..114..
+            part1(
+                &parse(
+                    "467..114..
                  ...*......
                  ..35..633.
                  ......#...
@@ -139,13 +150,24 @@ mod tests {
                  ......755.
                  ...$.*....
                  .664.598.."
-            )),
+                )
+                .unwrap()
+            ),
             4361
         );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse("<EXAMPLE>")), "<RESULT>");
+        assert_eq!(part2(&parse("<EXAMPLE>").unwrap()), "<RESULT>");
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        let err = parse("467..\n...*\n..35.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a row of 5 cells, found [...*]"
+        );
     }
 }
@@ -1,13 +1,20 @@
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Input = (Vec<i32>, Vec<i32>);
 
 #[aoc_generator(day1)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    source
         .lines()
-        .map(|l| l.trim().split_once("   ").unwrap())
-        .map(|(a, b)| (a.parse::<i32>().unwrap(), b.parse::<i32>().unwrap()))
+        .map(|l| {
+            let mut words = source.words(l);
+            let a: i32 = words.parse("a location id")?;
+            let b: i32 = words.parse("a location id")?;
+            words.end()?;
+            Ok((a, b))
+        })
         .collect()
 }
 
@@ -51,14 +58,17 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "3   4
+            part1(
+                &parse(
+                    "3   4
                  4   3
                  2   5
                  1   3
                  3   9
                  3   3"
-            )),
+                )
+                .unwrap()
+            ),
             11
         );
     }
@@ -66,15 +76,27 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "3   4
+            part2(
+                &parse(
+                    "3   4
                  4   3
                  2   5
                  1   3
                  3   9
                  3   3"
-            )),
+                )
+                .unwrap()
+            ),
             31
         );
     }
+
+    #[test]
+    fn test_parse_extra_id() {
+        let err = parse("3   4\n4   3   5").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 9: expected end of line, found [5]"
+        );
+    }
 }
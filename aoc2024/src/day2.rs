@@ -1,18 +1,18 @@
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::debug;
 
 type Level = u32;
 type Report = Vec<Level>;
 type Input = Vec<Report>;
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Input {
-    input
+fn parse(input: &str) -> Result<Input, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|c| c.parse::<u32>().unwrap())
-                .collect()
-        })
+        .map(|l| source.whitespace_separated(l))
         .collect()
 }
 
@@ -20,7 +20,7 @@ fn check_report(report: &Report, i: usize) -> bool {
     let mut incr = None;
     let mut prev = None;
     for lvl in report {
-        //eprintln!("{prev:?} {lvl} {incr:?}");
+        //debug!("{prev:?} {lvl} {incr:?}");
         if let Some(prv) = prev {
             if incr.is_none() {
                 incr = Some(lvl > prv);
@@ -28,7 +28,7 @@ fn check_report(report: &Report, i: usize) -> bool {
             match incr {
                 Some(true) => {
                     if lvl <= prv || lvl - prv > 3 {
-                        eprintln!(
+                        debug!(
                             "{}: Increase error: {lvl} does not increase correctly from {prv}",
                             i + 1
                         );
@@ -37,7 +37,7 @@ fn check_report(report: &Report, i: usize) -> bool {
                 }
                 Some(false) => {
                     if lvl >= prv || prv - lvl > 3 {
-                        eprintln!(
+                        debug!(
                             "{}: Decrease error: {lvl} does not decrease correctly from {prv}",
                             i + 1
                         );
@@ -49,7 +49,7 @@ fn check_report(report: &Report, i: usize) -> bool {
         }
         prev = Some(lvl);
     }
-    eprintln!("{}: Safe", i + 1);
+    debug!("{}: Safe", i + 1);
     true
 }
 
@@ -60,7 +60,7 @@ fn part1(input: &Input) -> usize {
         .enumerate()
         .filter(|(i, report)| check_report(report, *i))
         //.inspect(|_| {
-        //    eprintln!("ok");
+        //    debug!("ok");
         //})
         .count()
 }
@@ -98,14 +98,17 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "7 6 4 2 1
+            part1(
+                &parse(
+                    "7 6 4 2 1
                  1 2 7 8 9
                  9 7 6 2 1
                  1 3 2 4 5
                  8 6 4 4 1
                  1 3 6 7 9"
-            )),
+                )
+                .unwrap()
+            ),
             2
         );
     }
@@ -113,14 +116,17 @@ mod tests {
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse(
-                "7 6 4 2 1
+            part2(
+                &parse(
+                    "7 6 4 2 1
                  1 2 7 8 9
                  9 7 6 2 1
                  1 3 2 4 5
                  8 6 4 4 1
                  1 3 6 7 9"
-            )),
+                )
+                .unwrap()
+            ),
             4
         );
     }
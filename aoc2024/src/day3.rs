@@ -1,7 +1,9 @@
 use aoc_runner_derive::{aoc, aoc_generator};
+use log::trace;
 
 #[aoc_generator(day3)]
 fn parse(input: &str) -> String {
+    aoc_common::logging::init();
     input.to_string()
 }
 
@@ -12,7 +14,7 @@ fn part1(input: &str) -> u32 {
     let re = regex::Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
 
     for (_, [d1, d2]) in re.captures_iter(input).map(|c| c.extract()) {
-        println!("mul({d1},{d2})");
+        trace!("mul({d1},{d2})");
         let d1: u32 = d1.parse().unwrap();
         let d2: u32 = d2.parse().unwrap();
         sum += d1 * d2;
@@ -40,7 +42,7 @@ fn part2(input: &str) -> u32 {
                 if doing {
                     let d1: u32 = matches.get(1).unwrap().as_str().parse().unwrap();
                     let d2: u32 = matches.get(2).unwrap().as_str().parse().unwrap();
-                    println!("mul({d1},{d2})");
+                    trace!("mul({d1},{d2})");
                     sum += d1 * d2;
                 }
             }
@@ -1,12 +1,10 @@
+use aoc_common::Turn;
+use aoc_common::parse::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
-
-enum Dir {
-    R,
-    L,
-}
+use log::trace;
 
 struct Rotation {
-    dir: Dir,
+    dir: Turn,
     num: usize,
 }
 
@@ -24,14 +22,14 @@ impl Dial {
     }
     fn rotate(&mut self, rotation: &Rotation) {
         match rotation.dir {
-            Dir::R => {
+            Turn::Right => {
                 self.current_position = (self.current_position + rotation.num) % 100;
-                eprintln!(
+                trace!(
                     "The dial is rotated R{} to point at {}",
                     rotation.num, self.current_position
                 );
             }
-            Dir::L => {
+            Turn::Left => {
                 let normalized_num = rotation.num % 100;
                 let new_position = if normalized_num > self.current_position {
                     100 + self.current_position - normalized_num
@@ -39,7 +37,7 @@ impl Dial {
                     self.current_position - normalized_num
                 };
                 self.current_position = new_position;
-                eprintln!(
+                trace!(
                     "The dial is rotated L{} to point at {}",
                     rotation.num, self.current_position
                 );
@@ -50,27 +48,26 @@ impl Dial {
             self.passed_zero_times += 1;
         }
 
-        eprintln!("Passed zero {} times", self.passed_zero_times);
+        trace!("Passed zero {} times", self.passed_zero_times);
     }
 }
 
 #[aoc_generator(day1)]
-fn parse(input: &str) -> Vec<Rotation> {
-    input
-        .trim()
+fn parse(input: &str) -> Result<Vec<Rotation>, ParseError> {
+    aoc_common::logging::init();
+    let source = Source::new(input);
+    source
         .lines()
         .map(|l| {
-            let (dir_str, num_str) = l.trim().split_at(1);
+            let mut chars = l.chars();
+            let dir = chars
+                .next()
+                .and_then(Turn::from_char)
+                .ok_or_else(|| source.error(l, "[L] or [R]"))?;
 
-            let dir = match dir_str {
-                "R" => Dir::R,
-                "L" => Dir::L,
-                _ => panic!("Invalid direction"),
-            };
+            let num = source.parse(chars.as_str(), "a number of clicks")?;
 
-            let num = num_str.parse::<usize>().expect("Invalid number");
-
-            Rotation { dir, num }
+            Ok(Rotation { dir, num })
         })
         .collect()
 }
@@ -98,8 +95,9 @@ mod tests {
     #[test]
     fn part1_example() {
         assert_eq!(
-            part1(&parse(
-                "
+            part1(
+                &parse(
+                    "
                 L68
                 L30
                 R48
@@ -111,13 +109,15 @@ mod tests {
                 R14
                 L82
                 "
-            )),
+                )
+                .unwrap()
+            ),
             3
         );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse("<EXAMPLE>")), "<RESULT>");
+        assert_eq!(part2(&parse("<EXAMPLE>").unwrap()), "<RESULT>");
     }
 }